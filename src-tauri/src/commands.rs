@@ -1,9 +1,10 @@
-use crate::models::{Word, WordFilter, LearningStats, Settings};
-use crate::services::{xml_parser, learning, tts};
-use crate::utils::config;
+use crate::models::{Word, WordFilter, WordInput, WordPage, WordPageRequest, Example, DuplicateGroup, LearningStats, Settings, FieldError, HotkeyConfig, AppAction, Achievement, DateRange, ReviewRecord, SessionType, StudySession, RetentionBucket, TagPerformance, HardWord, HourlyPerformance, DailyReviewCount};
+use crate::services::{xml_parser, learning, tts, audio, pronunciation, speech_queue, wordbook, duplicates, journal, settings, file_watcher, hotkeys, actions, current_word, auto_switch, progress, reminders, achievements, review_log, sessions, analytics};
+use crate::utils::{config, atomic_file, stores};
+use crate::utils::hotkey::HotkeyIssue;
 use tauri::{command, Window, AppHandle};
-use tauri_plugin_store::StoreExt;
 use anyhow::Result;
+use chrono::NaiveDate;
 use crate::models::word::*;
 
 #[command]
@@ -13,6 +14,24 @@ pub async fn get_words(filter: Option<WordFilter>) -> Result<Vec<Word>, String>
         .map_err(|e| e.to_string())
 }
 
+/// 分页、排序获取单词列表
+#[command]
+pub async fn get_words_page(app: AppHandle, request: WordPageRequest) -> Result<WordPage, String> {
+    let progresses = progress_by_word(&app)?;
+    xml_parser::list_words(request, &progresses)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// 单词ID到学习进度的映射，单词本文件不保存进度，排序和比较进度时使用
+fn progress_by_word(app: &AppHandle) -> Result<std::collections::HashMap<String, WordProgress>, String> {
+    Ok(progress::load_all(app)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|p| (p.word_id.clone(), p))
+        .collect())
+}
+
 #[command]
 pub async fn get_word_by_id(id: String) -> Result<Option<Word>, String> {
     xml_parser::get_word_by_id(&id)
@@ -20,6 +39,176 @@ pub async fn get_word_by_id(id: String) -> Result<Option<Word>, String> {
         .map_err(|e| e.to_string())
 }
 
+#[command]
+pub async fn create_word(input: WordInput) -> Result<Word, String> {
+    let word = wordbook::create_word(input)
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    journal::record(&format!("新增单词 {}", word.word), vec![journal::Change::Word {
+        id: word.id.clone(),
+        before: None,
+        after: Some(word.clone()),
+    }]);
+    
+    Ok(word)
+}
+
+#[command]
+pub async fn update_word(id: String, input: WordInput) -> Result<Word, String> {
+    with_word_journal("编辑单词", vec![id.clone()], wordbook::update_word(&id, input)).await
+}
+
+/// 删除单词，同时清除其学习进度
+#[command]
+pub async fn delete_word(app: AppHandle, id: String) -> Result<(), String> {
+    journal::take_snapshot(&app, "delete_word")
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    let removed = wordbook::delete_word(&id)
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    let store = stores::open(&app, progress::PROGRESS_STORE).map_err(|e| e.to_string())?;
+    let progress_key = format!("word_{}", id);
+    let progress_before = store.get(&progress_key);
+    if store.delete(&progress_key) {
+        stores::save(&app, progress::PROGRESS_STORE).map_err(|e| e.to_string())?;
+    }
+    
+    let mut changes = vec![journal::Change::Word {
+        id: id.clone(),
+        before: Some(removed.clone()),
+        after: None,
+    }];
+    if let Some(value) = progress_before {
+        changes.push(journal::Change::StoreEntry {
+            store: "user_progress.json".to_string(),
+            key: progress_key,
+            before: Some(value),
+            after: None,
+        });
+    }
+    journal::record(&format!("删除单词 {}", removed.word), changes);
+    
+    Ok(())
+}
+
+#[command]
+pub async fn add_example(word_id: String, example: Example) -> Result<Word, String> {
+    with_word_journal("添加例句", vec![word_id.clone()], wordbook::add_example(&word_id, example)).await
+}
+
+#[command]
+pub async fn update_example(word_id: String, index: usize, example: Example) -> Result<Word, String> {
+    with_word_journal("编辑例句", vec![word_id.clone()], wordbook::update_example(&word_id, index, example)).await
+}
+
+#[command]
+pub async fn delete_example(word_id: String, index: usize) -> Result<Word, String> {
+    with_word_journal("删除例句", vec![word_id.clone()], wordbook::delete_example(&word_id, index)).await
+}
+
+#[command]
+pub async fn add_tags_to_words(word_ids: Vec<String>, tags: Vec<String>) -> Result<u32, String> {
+    with_word_journal("批量添加标签", word_ids.clone(), wordbook::add_tags(&word_ids, &tags)).await
+}
+
+#[command]
+pub async fn remove_tags_from_words(word_ids: Vec<String>, tags: Vec<String>) -> Result<u32, String> {
+    with_word_journal("批量移除标签", word_ids.clone(), wordbook::remove_tags(&word_ids, &tags)).await
+}
+
+/// 执行单词修改操作，并把受影响单词的前后状态记录到操作日志
+async fn with_word_journal<T>(
+    label: &str,
+    ids: Vec<String>,
+    operation: impl std::future::Future<Output = anyhow::Result<T>>,
+) -> Result<T, String> {
+    let before = journal::capture_words(&ids).await.map_err(|e| e.to_string())?;
+    let result = operation.await.map_err(|e| e.to_string())?;
+    let after = journal::capture_words(&ids).await.map_err(|e| e.to_string())?;
+    
+    journal::record(label, journal::word_changes(&ids, &before, &after));
+    Ok(result)
+}
+
+#[command]
+pub async fn find_duplicate_words(app: AppHandle) -> Result<Vec<DuplicateGroup>, String> {
+    let progresses = progress_by_word(&app)?;
+    duplicates::find_duplicates(&progresses)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 合并重复单词，并将学习进度记录合并到保留的单词上
+#[command]
+pub async fn merge_duplicate_words(
+    app: AppHandle,
+    primary_id: String,
+    duplicate_ids: Vec<String>,
+) -> Result<Word, String> {
+    journal::take_snapshot(&app, "merge_duplicate_words")
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    let all_ids: Vec<String> = std::iter::once(primary_id.clone())
+        .chain(duplicate_ids.iter().cloned())
+        .collect();
+    let words_before = journal::capture_words(&all_ids).await.map_err(|e| e.to_string())?;
+    
+    let progresses = progress_by_word(&app)?;
+    let (merged, _removed) = duplicates::merge_words(&primary_id, &duplicate_ids, &progresses)
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    let store = stores::open(&app, progress::PROGRESS_STORE).map_err(|e| e.to_string())?;
+    let progress_before: Vec<(String, Option<serde_json::Value>)> = all_ids
+        .iter()
+        .map(|id| {
+            let key = format!("word_{}", id);
+            let value = store.get(&key);
+            (key, value)
+        })
+        .collect();
+    
+    // 在所有相关单词中保留最好的一份学习进度
+    let best_progress = progress_before
+        .iter()
+        .filter_map(|(_, value)| value.clone())
+        .filter_map(|value| serde_json::from_value::<WordProgress>(value).ok())
+        .max_by_key(|p| duplicates::progress_rank(p.mastery_level, p.review_count, p.progress));
+    
+    for id in &duplicate_ids {
+        store.delete(format!("word_{}", id));
+    }
+    
+    if let Some(mut progress) = best_progress {
+        progress.word_id = primary_id.clone();
+        let progress_value = serde_json::to_value(&progress).map_err(|e| e.to_string())?;
+        store.set(format!("word_{}", primary_id), progress_value);
+    }
+    stores::save(&app, progress::PROGRESS_STORE).map_err(|e| e.to_string())?;
+    
+    let words_after = journal::capture_words(&all_ids).await.map_err(|e| e.to_string())?;
+    let mut changes = journal::word_changes(&all_ids, &words_before, &words_after);
+    for (key, before) in progress_before {
+        let after = store.get(&key);
+        if before != after {
+            changes.push(journal::Change::StoreEntry {
+                store: "user_progress.json".to_string(),
+                key,
+                before,
+                after,
+            });
+        }
+    }
+    journal::record(&format!("合并重复单词 {}", merged.word), changes);
+    
+    Ok(merged)
+}
+
 #[command]
 pub async fn update_word_progress(
     app: AppHandle,
@@ -27,93 +216,223 @@ pub async fn update_word_progress(
     progress: u8,
     mastery_level: u8,
     is_correct: bool,
-    time_spent: u64,
 ) -> Result<(), String> {
-    let store = app.store("user_progress.json").map_err(|e| e.to_string())?;
-    
-    let progress_key = format!("word_{}", word_id);
-    let mut word_progress = if let Some(existing) = store.get(&progress_key) {
-        serde_json::from_value::<WordProgress>(existing).unwrap_or_else(|_| WordProgress {
-            word_id: word_id.clone(),
-            progress,
-            mastery_level,
-            review_count: 0,
-            last_review: chrono::Utc::now(),
-            correct_count: 0,
-            incorrect_count: 0,
-            total_time_spent: 0,
-        })
-    } else {
-        WordProgress {
-            word_id: word_id.clone(),
-            progress,
-            mastery_level,
-            review_count: 0,
-            last_review: chrono::Utc::now(),
-            correct_count: 0,
-            incorrect_count: 0,
-            total_time_spent: 0,
-        }
-    };
-    
-    word_progress.progress = progress;
-    word_progress.mastery_level = mastery_level;
-    word_progress.review_count += 1;
-    word_progress.last_review = chrono::Utc::now();
-    word_progress.total_time_spent += time_spent;
+    progress::record_review(&app, &word_id, progress, mastery_level, is_correct)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+#[command]
+pub async fn get_settings() -> Result<Settings, String> {
+    config::load_settings()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 保存设置，字段取值无效时返回所有出错字段
+#[command]
+pub async fn update_settings(app: AppHandle, settings: serde_json::Value) -> Result<(), Vec<FieldError>> {
+    let new_settings = Settings::from_json(settings).map_err(|e| vec![e])?;
     
-    if is_correct {
-        word_progress.correct_count += 1;
+    settings::update(&app, new_settings)
+        .await
+        .map(|_| ())
+}
+
+/// 以 JSON Merge Patch 语义部分更新设置，返回更新后的完整设置
+#[command]
+pub async fn patch_settings(app: AppHandle, patch: serde_json::Value) -> Result<Settings, Vec<FieldError>> {
+    settings::apply_patch(&app, patch).await
+}
+
+/// 前端告知设置页面是否存在未保存的修改，用于处理外部修改冲突
+#[command]
+pub async fn set_unsaved_changes(unsaved: bool) -> Result<(), String> {
+    file_watcher::set_unsaved_changes(unsaved);
+    Ok(())
+}
+
+/// 处理外部修改冲突：接受外部修改或保留程序中的数据
+#[command]
+pub async fn resolve_external_change(
+    app: AppHandle,
+    file: file_watcher::WatchedFile,
+    accept: bool,
+) -> Result<(), String> {
+    file_watcher::resolve_conflict(&app, file, accept)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 各操作的全局快捷键注册结果
+#[command]
+pub async fn get_hotkey_status() -> Result<Vec<hotkeys::HotkeyStatus>, String> {
+    Ok(hotkeys::get_status())
+}
+
+/// 所有操作及其快捷键绑定
+#[command]
+pub async fn get_action_bindings() -> Result<Vec<actions::ActionBinding>, String> {
+    let settings = config::load_settings().await.map_err(|e| e.to_string())?;
+    Ok(actions::list_bindings(&settings.hotkeys))
+}
+
+/// 触发操作，供前端窗口内快捷键和按钮使用
+#[command]
+pub async fn trigger_action(app: AppHandle, action: AppAction) -> Result<(), String> {
+    actions::trigger(&app, action);
+    Ok(())
+}
+
+/// 后端维护的当前单词，窗口重新显示时用于恢复
+#[command]
+pub async fn get_current_word() -> Result<Option<Word>, String> {
+    Ok(current_word::get())
+}
+
+/// 前端通过搜索等方式切换单词时同步给后端
+#[command]
+pub async fn select_current_word(app: AppHandle, id: String) -> Result<Word, String> {
+    current_word::select(&app, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[command]
+pub async fn get_auto_switch_state() -> Result<auto_switch::AutoSwitchState, String> {
+    Ok(auto_switch::get_state())
+}
+
+#[command]
+pub async fn set_auto_switch(app: AppHandle, enabled: bool) -> Result<(), String> {
+    auto_switch::set_enabled(&app, enabled)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[command]
+pub async fn get_reminder_state() -> Result<reminders::ReminderState, String> {
+    Ok(reminders::get_state())
+}
+
+/// 临时免打扰指定分钟数，不传表示取消免打扰
+#[command]
+pub async fn set_do_not_disturb(app: AppHandle, minutes: Option<u32>) -> Result<(), String> {
+    reminders::set_do_not_disturb(&app, minutes)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 立即发送一次学习提醒，用于测试通知
+#[command]
+pub async fn send_study_reminder(app: AppHandle) -> Result<(), String> {
+    reminders::send_now(&app)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 数据目录 vocabulary 下的所有单词本及当前使用的单词本
+#[command]
+pub async fn get_wordbooks() -> Result<serde_json::Value, String> {
+    let wordbooks = wordbook::list_wordbooks().map_err(|e| e.to_string())?;
+    Ok(serde_json::json!({
+        "wordbooks": wordbooks,
+        "active": xml_parser::active_wordbook(),
+    }))
+}
+
+#[command]
+pub async fn switch_wordbook(app: AppHandle, name: String) -> Result<(), String> {
+    wordbook::switch_wordbook(&app, &name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[command]
+pub async fn get_wordbook_stats(app: AppHandle) -> Result<wordbook::WordbookStats, String> {
+    wordbook::stats(&app)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 保存前检查快捷键配置：无效、不安全、重复以及被系统占用的组合，并给出替代建议
+#[command]
+pub async fn check_hotkeys(app: AppHandle, config: HotkeyConfig) -> Result<Vec<HotkeyIssue>, String> {
+    Ok(hotkeys::check(&app, &config))
+}
+
+/// 朗读单词，默认打断正在进行的朗读；返回朗读任务 id，播放进度通过 speech-started / speech-finished 事件通知
+#[command]
+pub async fn play_pronunciation(app: AppHandle, word: String, interrupt: Option<bool>) -> Result<u64, String> {
+    // 只朗读单词，不朗读音标
+    Ok(queue_speech(&app, speech_queue::SpeechContent::Headword(word), interrupt))
+}
+
+/// 按设置中的朗读顺序朗读单词，未指定时朗读当前单词
+#[command]
+pub async fn play_word_sequence(app: AppHandle, word_id: Option<String>, interrupt: Option<bool>) -> Result<u64, String> {
+    let word = match word_id {
+        Some(id) => xml_parser::get_word_by_id(&id).await.map_err(|e| e.to_string())?,
+        None => current_word::get(),
+    };
+    let word = word.ok_or_else(|| "没有可朗读的单词".to_string())?;
+
+    Ok(queue_speech(&app, speech_queue::SpeechContent::Sequence(word), interrupt))
+}
+
+fn queue_speech(app: &AppHandle, content: speech_queue::SpeechContent, interrupt: Option<bool>) -> u64 {
+    if interrupt.unwrap_or(true) {
+        speech_queue::play(app, content)
     } else {
-        word_progress.incorrect_count += 1;
+        speech_queue::enqueue(app, content)
     }
-    
-    let progress_value = serde_json::to_value(&word_progress).map_err(|e| e.to_string())?;
-    store.set(&progress_key, progress_value);
-    store.save().map_err(|e| e.to_string())?;
-    
-    log::info!("✅ 单词 {} 学习进度已保存: 进度={}, 掌握度={}", word_id, progress, mastery_level);
+}
+
+#[command]
+pub async fn stop_speaking(app: AppHandle) -> Result<(), String> {
+    speech_queue::stop(&app);
     Ok(())
 }
 
+/// 导入发音包目录，文件以单词命名（如 apple.mp3）
 #[command]
-pub async fn get_settings() -> Result<Settings, String> {
-    config::load_settings()
+pub async fn import_audio_pack(path: String) -> Result<audio::AudioImportReport, String> {
+    audio::import_pack(std::path::Path::new(&path))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 各发音方式（发音文件、系统语音、本地命令）的状态和最近一次失败的原因
+#[command]
+pub async fn get_pronunciation_backends() -> Result<Vec<pronunciation::BackendStatus>, String> {
+    Ok(tts::backend_status().await)
+}
+
+#[command]
+pub async fn get_available_voices() -> Result<Vec<tts::VoiceInfo>, String> {
+    tts::get_available_voices()
         .await
         .map_err(|e| e.to_string())
 }
 
+/// 试听语音，不修改设置
 #[command]
-pub async fn update_settings(settings: Settings) -> Result<(), String> {
-    config::save_settings(&settings)
+pub async fn preview_voice(voice_id: String, text: Option<String>) -> Result<(), String> {
+    tts::preview_voice(&voice_id, text.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
 
 #[command]
-pub async fn play_pronunciation(word: String, phonetic: Option<String>) -> Result<(), String> {
-    tts::play_pronunciation(&word, phonetic.as_deref())
+pub async fn set_voice_by_name(app: AppHandle, voice_name: String) -> Result<(), String> {
+    tts::set_voice_by_name(&app, &voice_name)
         .await
         .map_err(|e| e.to_string())
 }
 
 #[command]
 pub async fn get_learning_stats(app: AppHandle) -> Result<LearningStats, String> {
-    let progress_store = app.store("user_progress.json").map_err(|e| e.to_string())?;
-    let stats_store = app.store("learning_stats.json").map_err(|e| e.to_string())?;
-    
-    let all_keys = progress_store.keys();
-    let mut word_progresses = Vec::new();
-    
-    for key in all_keys {
-        if key.starts_with("word_") {
-            if let Some(value) = progress_store.get(&key) {
-                if let Ok(progress) = serde_json::from_value::<WordProgress>(value) {
-                    word_progresses.push(progress);
-                }
-            }
-        }
-    }
+    let word_progresses = progress::load_all(&app).map_err(|e| e.to_string())?;
     
     let total_words = word_progresses.len() as u32;
     let learned_words = word_progresses.iter().filter(|p| p.review_count > 0).count() as u32;
@@ -134,17 +453,12 @@ pub async fn get_learning_stats(app: AppHandle) -> Result<LearningStats, String>
         0.0
     };
     
-    let daily_goal = if let Some(goal_value) = stats_store.get("daily_goal") {
-        serde_json::from_value::<u32>(goal_value).unwrap_or(20)
-    } else {
-        20
-    };
-    
-    let today = chrono::Utc::now().date_naive();
-    let daily_progress = word_progresses
-        .iter()
-        .filter(|p| p.last_review.date_naive() == today)
-        .count() as u32;
+    let daily_goal = config::load_settings()
+        .await
+        .map_err(|e| e.to_string())?
+        .learning
+        .daily_goal;
+    let daily_progress = progress::studied_today(&word_progresses);
     
     let streak_days = calculate_streak_days(&word_progresses);
     
@@ -161,18 +475,20 @@ pub async fn get_learning_stats(app: AppHandle) -> Result<LearningStats, String>
         total_time_spent,
     };
     
+    let stats_store = stores::open(&app, progress::STATS_STORE).map_err(|e| e.to_string())?;
     let stats_value = serde_json::to_value(&stats).map_err(|e| e.to_string())?;
     stats_store.set("latest_stats", stats_value);
-    stats_store.save().map_err(|e| e.to_string())?;
+    stores::save(&app, progress::STATS_STORE).map_err(|e| e.to_string())?;
     
     Ok(stats)
 }
 
 #[command]
 pub async fn export_progress(app: AppHandle, file_path: String) -> Result<(), String> {
-    let progress_store = app.store("user_progress.json").map_err(|e| e.to_string())?;
-    let stats_store = app.store("learning_stats.json").map_err(|e| e.to_string())?;
-    let sessions_store = app.store("study_sessions.json").map_err(|e| e.to_string())?;
+    let progress_store = stores::open(&app, progress::PROGRESS_STORE).map_err(|e| e.to_string())?;
+    let stats_store = stores::open(&app, progress::STATS_STORE).map_err(|e| e.to_string())?;
+    let sessions_store = stores::open(&app, sessions::SESSIONS_STORE).map_err(|e| e.to_string())?;
+    let review_log = review_log::load(&app, DateRange::default()).map_err(|e| e.to_string())?;
     
     let export_data = serde_json::json!({
         "export_date": chrono::Utc::now().to_rfc3339(),
@@ -180,10 +496,11 @@ pub async fn export_progress(app: AppHandle, file_path: String) -> Result<(), St
         "user_progress": progress_store.entries(),
         "learning_stats": stats_store.entries(),
         "study_sessions": sessions_store.entries(),
+        "review_log": review_log,
     });
     
     let json_content = serde_json::to_string_pretty(&export_data).map_err(|e| e.to_string())?;
-    std::fs::write(&file_path, json_content).map_err(|e| e.to_string())?;
+    atomic_file::write_atomic(std::path::Path::new(&file_path), json_content).map_err(|e| e.to_string())?;
     
     log::info!("✅ 学习进度已导出到: {}", file_path);
     Ok(())
@@ -194,16 +511,16 @@ pub async fn import_progress(app: AppHandle, file_path: String) -> Result<(), St
     let content = std::fs::read_to_string(&file_path).map_err(|e| e.to_string())?;
     let import_data: serde_json::Value = serde_json::from_str(&content).map_err(|e| e.to_string())?;
     
-    let progress_store = app.store("user_progress.json").map_err(|e| e.to_string())?;
-    let stats_store = app.store("learning_stats.json").map_err(|e| e.to_string())?;
-    let sessions_store = app.store("study_sessions.json").map_err(|e| e.to_string())?;
+    let progress_store = stores::open(&app, progress::PROGRESS_STORE).map_err(|e| e.to_string())?;
+    let stats_store = stores::open(&app, progress::STATS_STORE).map_err(|e| e.to_string())?;
+    let sessions_store = stores::open(&app, sessions::SESSIONS_STORE).map_err(|e| e.to_string())?;
     
     if let Some(user_progress) = import_data.get("user_progress") {
         if let Some(progress_obj) = user_progress.as_object() {
             for (key, value) in progress_obj {
                 progress_store.set(key, value.clone());
             }
-            progress_store.save().map_err(|e| e.to_string())?;
+            stores::save(&app, progress::PROGRESS_STORE).map_err(|e| e.to_string())?;
         }
     }
     
@@ -212,7 +529,7 @@ pub async fn import_progress(app: AppHandle, file_path: String) -> Result<(), St
             for (key, value) in stats_obj {
                 stats_store.set(key, value.clone());
             }
-            stats_store.save().map_err(|e| e.to_string())?;
+            stores::save(&app, progress::STATS_STORE).map_err(|e| e.to_string())?;
         }
     }
     
@@ -221,10 +538,23 @@ pub async fn import_progress(app: AppHandle, file_path: String) -> Result<(), St
             for (key, value) in sessions_obj {
                 sessions_store.set(key, value.clone());
             }
-            sessions_store.save().map_err(|e| e.to_string())?;
+            stores::save(&app, sessions::SESSIONS_STORE).map_err(|e| e.to_string())?;
         }
     }
     
+    // 复习记录导出为数组，旧版本导出的是以 review_<id> 为键的对象
+    let review_values: Vec<serde_json::Value> = match import_data.get("review_log") {
+        Some(serde_json::Value::Array(records)) => records.clone(),
+        Some(serde_json::Value::Object(records)) => records.values().cloned().collect(),
+        _ => Vec::new(),
+    };
+    let review_records: Vec<ReviewRecord> = review_values
+        .into_iter()
+        .filter_map(|value| serde_json::from_value(value).ok())
+        .collect();
+    let imported = review_log::import(&app, review_records).map_err(|e| e.to_string())?;
+    log::info!("导入 {} 条复习记录", imported);
+    
     log::info!("✅ 学习进度已从文件导入: {}", file_path);
     Ok(())
 }
@@ -322,12 +652,12 @@ pub async fn clear_cache() -> Result<(), String> {
 
 fn calculate_streak_days(word_progresses: &[WordProgress]) -> u32 {
     let mut streak = 0;
-    let today = chrono::Utc::now().date_naive();
+    let today = chrono::Local::now().date_naive();
     
     for i in 0..365 {
         let check_date = today - chrono::Duration::days(i);
         let has_activity = word_progresses.iter().any(|p| {
-            p.last_review.date_naive() == check_date
+            p.last_review.with_timezone(&chrono::Local).date_naive() == check_date
         });
         
         if has_activity {
@@ -342,48 +672,162 @@ fn calculate_streak_days(word_progresses: &[WordProgress]) -> u32 {
     streak
 }
 
+/// 开始学习会话，之后的评分都会记到这个会话中
+#[command]
+pub async fn start_study_session(app: AppHandle, session_type: SessionType) -> Result<StudySession, String> {
+    sessions::start(&app, session_type).map_err(|e| e.to_string())
+}
+
 #[command]
-pub async fn save_study_session(
+pub async fn pause_study_session(app: AppHandle, session_id: String) -> Result<StudySession, String> {
+    sessions::pause(&app, &session_id).map_err(|e| e.to_string())
+}
+
+#[command]
+pub async fn resume_study_session(app: AppHandle, session_id: String) -> Result<StudySession, String> {
+    sessions::resume(&app, &session_id).map_err(|e| e.to_string())
+}
+
+/// 结束学习会话，返回根据复习记录汇总的结果
+#[command]
+pub async fn end_study_session(app: AppHandle, session_id: String) -> Result<StudySession, String> {
+    sessions::end(&app, &session_id).map_err(|e| e.to_string())
+}
+
+#[command]
+pub async fn get_study_session(app: AppHandle, session_id: String) -> Result<Option<StudySession>, String> {
+    sessions::get(&app, &session_id).map_err(|e| e.to_string())
+}
+
+/// 按本地日期查询学习会话（`YYYY-MM-DD`，两端都包含），最近的在前
+#[command]
+pub async fn get_study_sessions(
     app: AppHandle,
-    session_data: serde_json::Value,
-) -> Result<(), String> {
-    let store = app.store("study_sessions.json").map_err(|e| e.to_string())?;
-    
-    let session_id = format!("session_{}", chrono::Utc::now().timestamp());
-    
-    let mut session = session_data;
-    session["timestamp"] = serde_json::Value::String(chrono::Utc::now().to_rfc3339());
-    session["session_id"] = serde_json::Value::String(session_id.clone());
-    
-    store.set(&session_id, session);
-    store.save().map_err(|e| e.to_string())?;
-    
-    log::info!("✅ 学习会话已保存: {}", session_id);
-    Ok(())
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Result<Vec<StudySession>, String> {
+    sessions::list(&app, DateRange::new(from, to)).map_err(|e| e.to_string())
+}
+
+/// 按复习间隔统计的记忆保持率
+#[command]
+pub async fn get_retention_curve(
+    app: AppHandle,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Result<Vec<RetentionBucket>, String> {
+    analytics::retention_curve(&app, DateRange::new(from, to)).map_err(|e| e.to_string())
+}
+
+/// 各标签的正确率和平均用时
+#[command]
+pub async fn get_tag_performance(
+    app: AppHandle,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Result<Vec<TagPerformance>, String> {
+    analytics::tag_performance(&app, DateRange::new(from, to))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 正确率最低的单词，默认返回 20 个
+#[command]
+pub async fn get_hardest_words(
+    app: AppHandle,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    limit: Option<u32>,
+) -> Result<Vec<HardWord>, String> {
+    analytics::hardest_words(&app, DateRange::new(from, to), limit.unwrap_or(20) as usize)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 一天中各小时的答题情况
+#[command]
+pub async fn get_hourly_performance(
+    app: AppHandle,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Result<Vec<HourlyPerformance>, String> {
+    analytics::hourly_performance(&app, DateRange::new(from, to)).map_err(|e| e.to_string())
+}
+
+/// 日历热力图的每日复习次数
+#[command]
+pub async fn get_review_heatmap(
+    app: AppHandle,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Result<Vec<DailyReviewCount>, String> {
+    analytics::review_heatmap(&app, DateRange::new(from, to)).map_err(|e| e.to_string())
+}
+
+/// 所有成就及解锁时间
+#[command]
+pub async fn get_achievements(app: AppHandle) -> Result<Vec<Achievement>, String> {
+    achievements::list(&app).map_err(|e| e.to_string())
 }
 
 /// 重置所有学习进度
+///
+/// 重置前会写入磁盘快照，并记录到操作日志中以便撤销。
 #[command]
 pub async fn reset_all_progress(app: AppHandle) -> Result<(), String> {
-    // 清空所有学习进度存储
-    let progress_store = app.store("user_progress.json").map_err(|e| e.to_string())?;
-    let stats_store = app.store("learning_stats.json").map_err(|e| e.to_string())?;
-    let sessions_store = app.store("study_sessions.json").map_err(|e| e.to_string())?;
+    journal::take_snapshot(&app, "reset_all_progress")
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    // 先归档复习记录，失败时其他数据保持不变
+    let archive = review_log::new_archive_name();
+    review_log::archive(&app, &archive).map_err(|e| e.to_string())?;
+    let mut changes = vec![journal::Change::ReviewLogArchive { archive }];
     
-    // 清空所有数据
-    progress_store.clear();
-    stats_store.clear();
-    sessions_store.clear();
+    for name in journal::PROGRESS_STORES {
+        let store = stores::open(&app, name).map_err(|e| e.to_string())?;
+        
+        for (key, value) in store.entries() {
+            changes.push(journal::Change::StoreEntry {
+                store: name.to_string(),
+                key,
+                before: Some(value),
+                after: None,
+            });
+        }
+        
+        // 清空并保存
+        store.clear();
+        stores::save(&app, name).map_err(|e| e.to_string())?;
+    }
     
-    // 保存清空后的状态
-    progress_store.save().map_err(|e| e.to_string())?;
-    stats_store.save().map_err(|e| e.to_string())?;
-    sessions_store.save().map_err(|e| e.to_string())?;
+    journal::record("重置所有学习进度", changes);
     
     log::info!("✅ 所有学习进度已重置");
     Ok(())
 }
 
+/// 撤销最近一次操作
+#[command]
+pub async fn undo(app: AppHandle) -> Result<Option<journal::JournalSummary>, String> {
+    journal::undo(&app)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 重做最近一次撤销的操作
+#[command]
+pub async fn redo(app: AppHandle) -> Result<Option<journal::JournalSummary>, String> {
+    journal::redo(&app)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[command]
+pub async fn get_journal() -> Result<journal::JournalState, String> {
+    Ok(journal::get_state())
+}
+
 // ========================
 // 增量更新相关命令
 // ========================
@@ -519,6 +963,8 @@ pub async fn prepare_app_restart() -> Result<(), String> {
     Ok(())
 }
 
+const UPDATE_LOGS_STORE: &str = "update_logs.json";
+
 /// 记录更新日志
 #[command]
 pub async fn log_update_event(
@@ -526,7 +972,7 @@ pub async fn log_update_event(
     event_type: String,
     details: serde_json::Value
 ) -> Result<(), String> {
-    let store = app.store("update_logs.json").map_err(|e| e.to_string())?;
+    let store = stores::open(&app, UPDATE_LOGS_STORE).map_err(|e| e.to_string())?;
     
     let log_entry = serde_json::json!({
         "timestamp": chrono::Utc::now().to_rfc3339(),
@@ -537,7 +983,7 @@ pub async fn log_update_event(
     
     let log_id = format!("update_log_{}", chrono::Utc::now().timestamp());
     store.set(&log_id, log_entry);
-    store.save().map_err(|e| e.to_string())?;
+    stores::save(&app, UPDATE_LOGS_STORE).map_err(|e| e.to_string())?;
     
     log::info!("📝 更新日志已记录: {}", event_type);
     
@@ -6,13 +6,8 @@ mod models;
 mod services;
 mod utils;
 
-use tauri::{
-    menu::{Menu, MenuItem, PredefinedMenuItem, MenuBuilder, SubmenuBuilder},
-    tray::{TrayIconBuilder, TrayIconEvent},
-    Manager, Emitter,
-};
+use tauri::Manager;
 use tauri_plugin_autostart::MacosLauncher;
-use tauri_plugin_global_shortcut::GlobalShortcutExt;
 use tauri_plugin_store::Builder as StoreBuilder;
 
 #[tokio::main]
@@ -48,20 +43,69 @@ async fn main() {
         .plugin(tauri_plugin_updater::Builder::new().build())
         .invoke_handler(tauri::generate_handler![
             commands::get_words,
+            commands::get_words_page,
             commands::get_word_by_id,
+            commands::create_word,
+            commands::update_word,
+            commands::delete_word,
+            commands::add_example,
+            commands::update_example,
+            commands::delete_example,
+            commands::add_tags_to_words,
+            commands::remove_tags_from_words,
+            commands::find_duplicate_words,
+            commands::merge_duplicate_words,
             commands::update_word_progress,
             commands::get_settings,
             commands::update_settings,
+            commands::patch_settings,
+            commands::set_unsaved_changes,
+            commands::resolve_external_change,
+            commands::get_hotkey_status,
+            commands::check_hotkeys,
+            commands::get_action_bindings,
+            commands::trigger_action,
+            commands::get_current_word,
+            commands::select_current_word,
+            commands::get_auto_switch_state,
+            commands::set_auto_switch,
+            commands::get_reminder_state,
+            commands::set_do_not_disturb,
+            commands::send_study_reminder,
+            commands::get_wordbooks,
+            commands::switch_wordbook,
+            commands::get_wordbook_stats,
             commands::play_pronunciation,
+            commands::play_word_sequence,
+            commands::stop_speaking,
+            commands::import_audio_pack,
+            commands::get_pronunciation_backends,
+            commands::get_available_voices,
+            commands::preview_voice,
+            commands::set_voice_by_name,
             commands::get_learning_stats,
             commands::export_progress,
             commands::import_progress,
             commands::search_words,
             commands::reset_all_progress,
+            commands::undo,
+            commands::redo,
+            commands::get_journal,
             commands::toggle_window_on_top,
             commands::minimize_to_tray,
             commands::clear_cache,
-            commands::save_study_session,
+            commands::start_study_session,
+            commands::pause_study_session,
+            commands::resume_study_session,
+            commands::end_study_session,
+            commands::get_study_session,
+            commands::get_study_sessions,
+            commands::get_retention_curve,
+            commands::get_tag_performance,
+            commands::get_hardest_words,
+            commands::get_hourly_performance,
+            commands::get_review_heatmap,
+            commands::get_achievements,
             // 增量更新相关命令
             commands::get_app_version,
             commands::open_url,
@@ -77,178 +121,86 @@ async fn main() {
         .setup(|app| {
             let _handle = app.handle().clone();
             
-            // 创建原生应用菜单
-            // 应用菜单 - 小驴单词薄
-            let about_app = MenuItem::with_id(app, "about", "关于小驴单词薄", true, None::<&str>)?;
-            let preferences = MenuItem::with_id(app, "preferences", "偏好设置...", true, Some("CmdOrCtrl+Comma"))?;
-            let quit_app = MenuItem::with_id(app, "quit_app", "退出小驴单词薄", true, Some("CmdOrCtrl+Q"))?;
+            // 学习数据的 store 在前端加载之前打开：关闭插件的自动保存，文件损坏时从备份恢复
+            for name in services::journal::PROGRESS_STORES {
+                utils::stores::open(app.handle(), name)?;
+            }
             
-            let app_menu = SubmenuBuilder::new(app, "小驴单词薄")
-                .item(&about_app)
-                .separator()
-                .item(&preferences)
-                .separator()
-                .item(&quit_app)
-                .build()?;
+            // 设置变更时立即应用到窗口、托盘、快捷键和语音
+            services::window::register_settings_subscriber();
+            services::tray::register_settings_subscriber();
+            services::hotkeys::register_settings_subscriber();
+            services::tts::register_settings_subscriber();
+            services::audio::register_settings_subscriber();
+            services::progress::register_settings_subscriber();
             
-            // 学习菜单
-            let next_word = MenuItem::with_id(app, "next_word", "下一个单词", true, Some("CmdOrCtrl+Right"))?;
-            let prev_word = MenuItem::with_id(app, "prev_word", "上一个单词", true, Some("CmdOrCtrl+Left"))?;
-            let auto_switch = MenuItem::with_id(app, "auto_switch", "自动切换", true, Some("Space"))?;
-            let refresh_words = MenuItem::with_id(app, "refresh_words", "刷新单词库", true, Some("CmdOrCtrl+R"))?;
-            
-            let learning_menu = SubmenuBuilder::new(app, "学习")
-                .item(&next_word)
-                .item(&prev_word)
-                .separator()
-                .item(&auto_switch)
-                .separator()
-                .item(&refresh_words)
-                .build()?;
-            
-            // 工具菜单
-            let open_settings = MenuItem::with_id(app, "open_settings", "设置", true, Some("CmdOrCtrl+Comma"))?;
-            let toggle_stats = MenuItem::with_id(app, "toggle_stats", "学习统计", true, Some("CmdOrCtrl+S"))?;
-            let theme_menu = MenuItem::with_id(app, "theme_menu", "主题切换", true, None::<&str>)?;
+            // 监听设置文件和词汇文件的外部修改
+            let watcher_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = services::file_watcher::start(&watcher_handle).await {
+                    log::error!("❌ 启动文件监听失败: {}", e);
+                }
+            });
             
-            let tools_menu = SubmenuBuilder::new(app, "工具")
-                .item(&open_settings)
-                .item(&toggle_stats)
-                .separator()
-                .item(&theme_menu)
-                .build()?;
+            // 先按设置选择单词本，再由后端计时自动切换单词，窗口隐藏时同样生效
+            let auto_switch_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = services::wordbook::init(&auto_switch_handle).await {
+                    log::error!("❌ 加载单词本设置失败: {}", e);
+                }
+                if let Err(e) = services::auto_switch::start(&auto_switch_handle).await {
+                    log::error!("❌ 启动自动切换失败: {}", e);
+                }
+            });
             
-            // 构建完整的应用菜单
-            let main_menu = MenuBuilder::new(app)
-                .items(&[&app_menu, &learning_menu, &tools_menu])
-                .build()?;
+            // 未完成每日目标或有待复习单词时按间隔发送学习提醒
+            let reminder_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = services::reminders::start(&reminder_handle).await {
+                    log::error!("❌ 启动学习提醒失败: {}", e);
+                }
+            });
             
-            // 设置为应用菜单
-            app.set_menu(main_menu)?;
+            // 应用菜单，随自动切换、窗口置顶和单词本的变化重建
+            services::menu::init(app.handle())?;
             
-            // 添加菜单事件处理
-            app.on_menu_event(move |app_handle, event| {
-                match event.id().as_ref() {
-                    "about" => {
-                        // TODO: 显示关于对话框
-                        println!("显示关于对话框");
-                    }
-                    "preferences" | "open_settings" => {
-                        let windows = app_handle.webview_windows();
-                        if let Some(window) = windows.values().next() {
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                            // 发送事件到前端打开设置面板
-                            let _ = window.emit("open-settings", ());
-                        }
-                    }
-                    "quit_app" => {
-                        app_handle.exit(0);
-                    }
-                    "next_word" => {
-                        let windows = app_handle.webview_windows();
-                        if let Some(window) = windows.values().next() {
-                            let _ = window.emit("menu-next-word", ());
-                        }
-                    }
-                    "prev_word" => {
-                        let windows = app_handle.webview_windows();
-                        if let Some(window) = windows.values().next() {
-                            let _ = window.emit("menu-prev-word", ());
-                        }
-                    }
-                    "auto_switch" => {
-                        let windows = app_handle.webview_windows();
-                        if let Some(window) = windows.values().next() {
-                            let _ = window.emit("menu-auto-switch", ());
-                        }
-                    }
-                    "refresh_words" => {
-                        let windows = app_handle.webview_windows();
-                        if let Some(window) = windows.values().next() {
-                            let _ = window.emit("menu-refresh-words", ());
-                        }
-                    }
-                    "toggle_stats" => {
-                        let windows = app_handle.webview_windows();
-                        if let Some(window) = windows.values().next() {
-                            let _ = window.emit("menu-toggle-stats", ());
-                        }
-                    }
-                    "theme_menu" => {
-                        let windows = app_handle.webview_windows();
-                        if let Some(window) = windows.values().next() {
-                            let _ = window.emit("menu-theme-switch", ());
-                        }
-                    }
-                    _ => {
-                        println!("未处理的菜单事件: {}", event.id().as_ref());
-                    }
+            // 系统托盘：显示当前单词、今日进度和快速评分
+            services::tray::build(app.handle())?;
+
+            // 托盘和窗口创建后立即应用设置中的托盘显示和窗口置顶
+            let initial_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                match utils::config::load_settings().await {
+                    Ok(settings) => services::settings::apply_initial(
+                        &initial_handle,
+                        settings,
+                        vec![
+                            services::settings::SettingsSection::ShowInTray,
+                            services::settings::SettingsSection::AlwaysOnTop,
+                        ],
+                    ),
+                    Err(e) => log::error!("❌ 加载窗口和托盘设置失败: {}", e),
                 }
             });
-            
-            // 创建托盘菜单（独立于应用菜单）
-            let quit = MenuItem::with_id(app, "tray_quit", "退出", true, None::<&str>)?;
-            let show = MenuItem::with_id(app, "tray_show", "显示", true, None::<&str>)?;
-            let hide = MenuItem::with_id(app, "tray_hide", "隐藏", true, None::<&str>)?;
-            let settings = MenuItem::with_id(app, "tray_settings", "设置", true, None::<&str>)?;
-            let separator = PredefinedMenuItem::separator(app)?;
 
-            let tray_menu = Menu::with_items(app, &[&show, &hide, &separator, &settings, &separator, &quit])?;
-            
-            // 设置系统托盘
-            let _tray = TrayIconBuilder::new()
-                .menu(&tray_menu)
-                .tooltip("小驴单词薄")
-                .icon(app.default_window_icon().unwrap().clone())
-                .on_menu_event(move |app, event| match event.id.as_ref() {
-                    "tray_quit" => {
-                        app.exit(0);
-                    }
-                    "tray_show" => {
-                        let windows = app.webview_windows();
-                        if let Some(window) = windows.values().next() {
-                            let _ = window.show();
-                        }
-                    }
-                    "tray_hide" => {
-                        let windows = app.webview_windows();
-                        if let Some(window) = windows.values().next() {
-                            let _ = window.hide();
-                        }
-                    }
-                    "tray_settings" => {
-                        let windows = app.webview_windows();
-                        if let Some(window) = windows.values().next() {
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                            // 发送事件到前端打开设置面板
-                            let _ = window.emit("open-settings", ());
-                        }
-                    }
-                    _ => {}
-                })
-                .on_tray_icon_event(|tray, event| {
-                    if let TrayIconEvent::Click {
-                        button: tauri::tray::MouseButton::Left,
-                        button_state: tauri::tray::MouseButtonState::Up,
-                        ..
-                    } = event
-                    {
-                        let app = tray.app_handle();
-                        if let Some(webview_window) = app.get_webview_window("main") {
-                            let _ = webview_window.show();
-                            let _ = webview_window.set_focus();
-                        }
-                    }
-                })
-                .build(app)?;
+            // 读取每日目标后刷新托盘中的今日进度
+            let progress_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = services::progress::init(&progress_handle).await {
+                    log::error!("❌ 读取每日目标失败: {}", e);
+                }
+            });
 
-            // 注册全局快捷键
-            app.global_shortcut().register("CmdOrCtrl+Shift+W")?;
-            app.global_shortcut().register("CmdOrCtrl+Right")?;
-            app.global_shortcut().register("CmdOrCtrl+Left")?;
-            app.global_shortcut().register("Space")?;
+            // 按设置注册全局快捷键，单个快捷键失败不影响启动
+            let hotkey_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                match utils::config::load_settings().await {
+                    Ok(settings) => {
+                        services::hotkeys::register_all(&hotkey_handle, &settings.hotkeys);
+                    }
+                    Err(e) => log::error!("❌ 加载快捷键配置失败: {}", e),
+                }
+            });
 
             Ok(())
         })
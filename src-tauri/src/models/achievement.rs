@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// 成就的类别，设置界面按类别分组显示
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum AchievementCategory {
+    Streak,   // 连续学习天数
+    Mastery,  // 掌握单词数
+    Session,  // 单次学习
+    Tag,      // 学完一个标签
+    Schedule, // 学习时间段（夜猫子、早起鸟）
+}
+
+/// 一个成就及其解锁状态；标签成就按标签动态生成，id 为 `tag:<标签名>`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Achievement {
+    pub id: String,
+    pub category: AchievementCategory,
+    pub title: String,
+    pub description: String,
+    #[ts(type = "string | null")]
+    pub unlocked_at: Option<DateTime<Utc>>, // None 表示尚未解锁
+}
@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// 可以由快捷键、菜单和托盘触发的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum AppAction {
+    ToggleWindow,
+    NextWord,
+    PrevWord,
+    RevealAnswer,
+    #[serde(rename = "grade_1")]
+    Grade1, // 完全不会
+    #[serde(rename = "grade_2")]
+    Grade2, // 困难
+    #[serde(rename = "grade_3")]
+    Grade3, // 良好
+    #[serde(rename = "grade_4")]
+    Grade4, // 简单
+    MarkKnown,
+    SpeakWord,
+    SpeakExample,
+    ToggleAutoSwitch,
+    OpenSearch,
+    ToggleAlwaysOnTop,
+    StudyNow, // 显示窗口开始学习，学习提醒和托盘使用
+}
+
+/// 快捷键的生效范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum HotkeyScope {
+    Global, // 系统全局，窗口隐藏或失去焦点时也生效
+    #[default]
+    Window, // 仅在主窗口获得焦点时生效
+}
+
+impl AppAction {
+    pub const ALL: [AppAction; 15] = [
+        AppAction::ToggleWindow,
+        AppAction::NextWord,
+        AppAction::PrevWord,
+        AppAction::RevealAnswer,
+        AppAction::Grade1,
+        AppAction::Grade2,
+        AppAction::Grade3,
+        AppAction::Grade4,
+        AppAction::MarkKnown,
+        AppAction::SpeakWord,
+        AppAction::SpeakExample,
+        AppAction::ToggleAutoSwitch,
+        AppAction::OpenSearch,
+        AppAction::ToggleAlwaysOnTop,
+        AppAction::StudyNow,
+    ];
+
+    /// 与序列化名称一致的标识，同时用作菜单项 id
+    pub fn id(self) -> &'static str {
+        match self {
+            AppAction::ToggleWindow => "toggle_window",
+            AppAction::NextWord => "next_word",
+            AppAction::PrevWord => "prev_word",
+            AppAction::RevealAnswer => "reveal_answer",
+            AppAction::Grade1 => "grade_1",
+            AppAction::Grade2 => "grade_2",
+            AppAction::Grade3 => "grade_3",
+            AppAction::Grade4 => "grade_4",
+            AppAction::MarkKnown => "mark_known",
+            AppAction::SpeakWord => "speak_word",
+            AppAction::SpeakExample => "speak_example",
+            AppAction::ToggleAutoSwitch => "toggle_auto_switch",
+            AppAction::OpenSearch => "open_search",
+            AppAction::ToggleAlwaysOnTop => "toggle_always_on_top",
+            AppAction::StudyNow => "study_now",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.id() == id)
+    }
+
+    /// 菜单和设置界面中显示的名称
+    pub fn label(self) -> &'static str {
+        match self {
+            AppAction::ToggleWindow => "显示/隐藏窗口",
+            AppAction::NextWord => "下一个单词",
+            AppAction::PrevWord => "上一个单词",
+            AppAction::RevealAnswer => "显示释义",
+            AppAction::Grade1 => "评分：不会",
+            AppAction::Grade2 => "评分：困难",
+            AppAction::Grade3 => "评分：良好",
+            AppAction::Grade4 => "评分：简单",
+            AppAction::MarkKnown => "标记为已掌握",
+            AppAction::SpeakWord => "朗读单词",
+            AppAction::SpeakExample => "朗读例句",
+            AppAction::ToggleAutoSwitch => "自动切换",
+            AppAction::OpenSearch => "搜索",
+            AppAction::ToggleAlwaysOnTop => "窗口置顶",
+            AppAction::StudyNow => "开始学习",
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// 按复习间隔分组的记忆保持率：距离上次复习若干天后再次复习时答对的比例
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct RetentionBucket {
+    pub label: String,
+    pub min_days: u32,
+    pub max_days: Option<u32>, // 不包含；None 表示不设上限
+    pub reviews: u32,
+    pub recalled: u32,
+    pub retention: f64, // 0-1，没有复习时为 0
+}
+
+/// 一个标签下单词的答题情况
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TagPerformance {
+    pub tag: String,
+    pub reviews: u32,
+    pub correct: u32,
+    pub accuracy: f64,
+    pub average_time_spent: f64, // 每次复习的平均用时（秒）
+}
+
+/// 答错最多的单词
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct HardWord {
+    pub word_id: String,
+    pub word: String, // 单词已从单词本中删除时为空
+    pub reviews: u32,
+    pub incorrect: u32,
+    pub accuracy: f64,
+    pub average_time_spent: f64,
+}
+
+/// 一天中某个小时（本地时间）的答题情况
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct HourlyPerformance {
+    pub hour: u32,
+    pub reviews: u32,
+    pub correct: u32,
+    pub accuracy: f64,
+    pub average_time_spent: f64,
+}
+
+/// 日历热力图中的一天（本地日期）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct DailyReviewCount {
+    #[ts(type = "string")]
+    pub date: NaiveDate,
+    pub reviews: u32,
+    pub correct: u32,
+}
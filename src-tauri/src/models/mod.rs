@@ -1,5 +1,13 @@
 pub mod word;
 pub mod settings;
+pub mod action;
+pub mod achievement;
+pub mod session;
+pub mod analytics;
 
 pub use word::*;
-pub use settings::*; 
\ No newline at end of file
+pub use settings::*;
+pub use action::*;
+pub use achievement::*;
+pub use session::*;
+pub use analytics::*;
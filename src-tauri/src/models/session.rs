@@ -0,0 +1,90 @@
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// 一次复习记录，每次评分追加一条，用于统计学习会话和学习分析
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ReviewRecord {
+    pub id: String,
+    pub word_id: String,
+    pub session_id: Option<String>, // 评分时正在进行的学习会话
+    #[ts(type = "string")]
+    pub reviewed_at: DateTime<Utc>,
+    #[ts(type = "string | null")]
+    pub previous_review: Option<DateTime<Utc>>, // 该单词上一次复习的时间，首次学习为 None
+    pub is_correct: bool,
+    #[ts(type = "number")]
+    pub time_spent: u64, // 秒
+    pub progress: u8,
+    pub mastery_level: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum SessionType {
+    Review,
+    NewWords,
+    Mixed,
+}
+
+/// 学习会话中的一次暂停，`ended_at` 为 None 表示仍在暂停
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SessionPause {
+    #[ts(type = "string")]
+    pub started_at: DateTime<Utc>,
+    #[ts(type = "string | null")]
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+/// 一次学习会话；答题数等统计由后端根据复习记录汇总
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct StudySession {
+    pub session_id: String,
+    pub session_type: SessionType,
+    #[ts(type = "string")]
+    pub started_at: DateTime<Utc>,
+    #[ts(type = "string | null")]
+    pub ended_at: Option<DateTime<Utc>>, // None 表示会话仍在进行
+    pub pauses: Vec<SessionPause>,
+    pub words_studied: Vec<String>, // 学习的单词ID列表
+    #[ts(type = "number")]
+    pub session_duration: u64, // 会话时长（秒），不含暂停时间
+    pub correct_answers: u32,
+    pub total_answers: u32,
+    pub average_time_per_word: f64,
+}
+
+impl StudySession {
+    pub fn is_active(&self) -> bool {
+        self.ended_at.is_none()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.pauses.last().is_some_and(|pause| pause.ended_at.is_none())
+    }
+}
+
+/// 按本地日期查询的范围，两端都包含；未指定的一端不限制
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DateRange {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl DateRange {
+    pub fn new(from: Option<NaiveDate>, to: Option<NaiveDate>) -> Self {
+        Self { from, to }
+    }
+
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        self.contains_date(time.with_timezone(&Local).date_naive())
+    }
+
+    pub fn contains_date(&self, date: NaiveDate) -> bool {
+        !self.from.is_some_and(|from| date < from) && !self.to.is_some_and(|to| date > to)
+    }
+}
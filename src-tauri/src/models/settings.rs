@@ -1,12 +1,24 @@
+use crate::models::{AppAction, HotkeyScope};
+use chrono::{DateTime, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use ts_rs::TS;
+use crate::utils::settings_migration::CURRENT_SETTINGS_VERSION;
+use crate::services::xml_parser::DEFAULT_WORDBOOK;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export)]
 pub struct Settings {
+    pub version: u32, // 配置结构版本，见 utils::settings_migration
     pub auto_switch: bool,
     pub switch_interval: u32,
+    pub pause_policy: PausePolicy, // 自动切换的暂停条件
     pub always_on_top: bool,
     pub show_in_tray: bool,
     pub enable_tts: bool,
+    pub tts: TtsConfig,
+    pub active_wordbook: String, // vocabulary 目录下的单词本文件名
     pub window_size: WindowSize,
     pub hotkeys: HotkeyConfig,
     pub theme: ThemeConfig,
@@ -14,25 +26,45 @@ pub struct Settings {
     pub notification: NotificationConfig,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct PausePolicy {
+    pub when_idle: bool,
+    pub idle_seconds: u32, // 无键鼠操作超过该时长视为离开
+    pub when_fullscreen: bool, // 全屏程序（游戏、演示）运行时暂停
+    pub while_speaking: bool,  // 朗读未结束时不计时，读完再切换
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export)]
 pub struct WindowSize {
     pub width: u32,
     pub height: u32,
-    pub ratio_preset: String, // "phone", "square", "golden", "widescreen", "custom"
+    pub ratio_preset: RatioPreset,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export)]
 pub struct HotkeyConfig {
-    pub toggle_window: String,
-    pub next_word: String,
-    pub prev_word: String,
-    pub play_pronunciation: String,
-    pub pause_learning: String,
+    pub bindings: BTreeMap<AppAction, HotkeyBinding>, // 未出现的操作没有绑定快捷键
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct HotkeyBinding {
+    pub shortcut: String, // 空字符串表示不绑定
+    pub scope: HotkeyScope,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export)]
 pub struct ThemeConfig {
-    pub mode: String, // "light", "dark", "auto"
+    pub mode: ThemeMode,
     pub accent_color: String,
     pub use_mica_effect: bool,
     pub transparency: f64,
@@ -40,33 +72,184 @@ pub struct ThemeConfig {
     pub font_size: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export)]
 pub struct LearningConfig {
     pub daily_goal: u32,
     pub auto_next: bool,
     pub show_pronunciation: bool,
     pub show_examples: bool,
-    pub review_mode: String, // "smart", "sequential", "random"
-    pub difficulty_preference: String, // "easy", "medium", "hard", "mixed"
+    pub review_mode: ReviewMode,
+    pub difficulty_preference: DifficultyPreference,
+}
+
+/// 朗读设置，语速和音调是相对于系统语音默认值的倍数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct TtsConfig {
+    pub voice_id: String, // 空字符串表示按口音自动选择
+    pub rate: f32,
+    pub pitch: f32,
+    pub volume: f32, // 0-1
+    pub accent: Accent,
+    pub sequence: Vec<PlaybackStep>, // 朗读单词时依次播放的内容
+    pub speak_on_switch: bool,       // 自动切换到新单词时朗读，便于不看屏幕学习
+    pub repeat: u32,                 // 每次朗读重复的遍数
+    pub repeat_delay: u32,           // 两遍之间的间隔（毫秒）
+    pub backends: Vec<PronunciationBackendKind>, // 按顺序尝试的发音方式，前一个不可用或失败时使用下一个
+    pub command: SpeechCommand,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum PronunciationBackendKind {
+    AudioFile, // 离线发音文件，只用于朗读单词本身
+    SystemTts,
+    Command, // 本地语音合成程序，如 piper、espeak-ng
+}
+
+/// 本地语音合成命令，程序把语音写入 WAV 文件后由应用播放
+///
+/// 参数中的 `{text}` 替换为朗读内容，`{output}` 替换为输出文件路径，`{voice}` 替换为朗读步骤指定的语音；
+/// 参数中没有 `{text}` 时朗读内容通过标准输入传入（piper 的用法）。
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct SpeechCommand {
+    pub program: String, // 空字符串表示未配置
+    pub args: Vec<String>,
+}
+
+/// 朗读顺序中的一步；`voice_id` 为空时英文使用设置中的语音，中文自动选择中文语音
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(export)]
+pub enum PlaybackStep {
+    Word {
+        #[serde(default)]
+        voice_id: String,
+    },
+    Example {
+        #[serde(default)]
+        index: u32, // 第几个例句，从 0 开始
+        #[serde(default)]
+        voice_id: String,
+    },
+    Translation {
+        #[serde(default)]
+        voice_id: String,
+    },
+    ExampleTranslation {
+        #[serde(default)]
+        index: u32,
+        #[serde(default)]
+        voice_id: String,
+    },
+    Pause {
+        millis: u32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum Accent {
+    #[default]
+    Us, // 美式发音 en-US
+    Uk, // 英式发音 en-GB
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum RatioPreset {
+    #[default]
+    Phone,      // 9:16 手机比例
+    Square,     // 1:1 正方形
+    Golden,     // 16:10 黄金比例
+    Widescreen, // 16:9 宽屏比例
+    Custom,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum ThemeMode {
+    Light,
+    Dark,
+    #[default]
+    Auto,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum ReviewMode {
+    #[default]
+    Smart,
+    Sequential,
+    Random,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum DifficultyPreference {
+    Easy,
+    Medium,
+    Hard,
+    #[default]
+    Mixed,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 设置校验失败时返回给前端的字段级错误
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct FieldError {
+    pub field: String, // 以点分隔的字段路径，如 "theme.mode"
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export)]
 pub struct NotificationConfig {
     pub enable_notifications: bool,
     pub study_reminders: bool,
     pub reminder_interval: u32, // 分钟
     pub achievement_notifications: bool,
     pub sound_enabled: bool,
+    pub quiet_hours: QuietHours,
+    pub reminder_weekdays: Vec<u32>, // 发送提醒的星期，0 为周日
+    #[ts(type = "string | null")]
+    pub do_not_disturb_until: Option<DateTime<Utc>>, // 临时免打扰，到期后自动恢复
+}
+
+/// 每天不发送提醒的时段，结束时间早于开始时间表示跨过午夜
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct QuietHours {
+    pub enabled: bool,
+    pub start: String, // HH:MM，本地时间
+    pub end: String,
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
+            version: CURRENT_SETTINGS_VERSION,
             auto_switch: true,
             switch_interval: 7,
+            pause_policy: PausePolicy::default(),
             always_on_top: false,
             show_in_tray: true,
             enable_tts: true,
+            tts: TtsConfig::default(),
+            active_wordbook: DEFAULT_WORDBOOK.to_string(),
             window_size: WindowSize::default(),
             hotkeys: HotkeyConfig::default(),
             theme: ThemeConfig::default(),
@@ -76,24 +259,54 @@ impl Default for Settings {
     }
 }
 
+impl Default for PausePolicy {
+    fn default() -> Self {
+        Self {
+            when_idle: true,
+            idle_seconds: 120,
+            when_fullscreen: true,
+            while_speaking: true,
+        }
+    }
+}
+
 impl Default for WindowSize {
     fn default() -> Self {
         Self {
             width: 320,
             height: 568,
-            ratio_preset: "phone".to_string(),
+            ratio_preset: RatioPreset::Phone,
         }
     }
 }
 
 impl Default for HotkeyConfig {
     fn default() -> Self {
+        use AppAction::*;
+        use HotkeyScope::{Global, Window};
+
+        let defaults = [
+            (ToggleWindow, "CmdOrCtrl+Shift+W", Global),
+            (NextWord, "CmdOrCtrl+Right", Global),
+            (PrevWord, "CmdOrCtrl+Left", Global),
+            (SpeakWord, "CmdOrCtrl+Shift+Space", Global),
+            (RevealAnswer, "Enter", Window),
+            (Grade1, "1", Window),
+            (Grade2, "2", Window),
+            (Grade3, "3", Window),
+            (Grade4, "4", Window),
+            (MarkKnown, "K", Window),
+            (SpeakExample, "E", Window),
+            (ToggleAutoSwitch, "CmdOrCtrl+P", Window),
+            (OpenSearch, "CmdOrCtrl+F", Window),
+            (ToggleAlwaysOnTop, "CmdOrCtrl+T", Window),
+        ];
+
         Self {
-            toggle_window: "CmdOrCtrl+Shift+W".to_string(),
-            next_word: "CmdOrCtrl+Right".to_string(),
-            prev_word: "CmdOrCtrl+Left".to_string(),
-            play_pronunciation: "Space".to_string(),
-            pause_learning: "CmdOrCtrl+P".to_string(),
+            bindings: defaults
+                .into_iter()
+                .map(|(action, shortcut, scope)| (action, HotkeyBinding { shortcut: shortcut.to_string(), scope }))
+                .collect(),
         }
     }
 }
@@ -101,7 +314,7 @@ impl Default for HotkeyConfig {
 impl Default for ThemeConfig {
     fn default() -> Self {
         Self {
-            mode: "auto".to_string(),
+            mode: ThemeMode::Auto,
             accent_color: "#0078d4".to_string(),
             use_mica_effect: true,
             transparency: 0.9,
@@ -111,6 +324,34 @@ impl Default for ThemeConfig {
     }
 }
 
+impl Default for TtsConfig {
+    fn default() -> Self {
+        Self {
+            voice_id: String::new(),
+            rate: 0.8, // 稍慢一点的语速
+            pitch: 1.0,
+            volume: 0.8,
+            accent: Accent::Us,
+            sequence: vec![
+                PlaybackStep::Word { voice_id: String::new() },
+                PlaybackStep::Pause { millis: 600 },
+                PlaybackStep::Example { index: 0, voice_id: String::new() },
+                PlaybackStep::Pause { millis: 400 },
+                PlaybackStep::Translation { voice_id: String::new() },
+            ],
+            speak_on_switch: false,
+            repeat: 1,
+            repeat_delay: 800,
+            backends: vec![
+                PronunciationBackendKind::AudioFile,
+                PronunciationBackendKind::SystemTts,
+                PronunciationBackendKind::Command,
+            ],
+            command: SpeechCommand::default(),
+        }
+    }
+}
+
 impl Default for LearningConfig {
     fn default() -> Self {
         Self {
@@ -118,8 +359,8 @@ impl Default for LearningConfig {
             auto_next: true,
             show_pronunciation: true,
             show_examples: true,
-            review_mode: "smart".to_string(),
-            difficulty_preference: "mixed".to_string(),
+            review_mode: ReviewMode::Smart,
+            difficulty_preference: DifficultyPreference::Mixed,
         }
     }
 }
@@ -132,27 +373,100 @@ impl Default for NotificationConfig {
             reminder_interval: 60,
             achievement_notifications: true,
             sound_enabled: true,
+            quiet_hours: QuietHours::default(),
+            reminder_weekdays: (0..7).collect(),
+            do_not_disturb_until: None,
         }
     }
 }
 
+impl Default for QuietHours {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            start: "22:00".to_string(),
+            end: "08:00".to_string(),
+        }
+    }
+}
+
+impl QuietHours {
+    /// 指定的本地时间是否在免打扰时段内，时间格式无效时视为不在
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let (Some(start), Some(end)) = (parse_time(&self.start), parse_time(&self.end)) else {
+            return false;
+        };
+
+        if start <= end {
+            start <= time && time < end
+        } else {
+            time >= start || time < end
+        }
+    }
+}
+
+/// 解析 HH:MM 格式的时间
+pub fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Settings {
+    /// 从 JSON 反序列化设置，失败时返回出错字段的路径
+    pub fn from_json(value: serde_json::Value) -> Result<Self, FieldError> {
+        serde_path_to_error::deserialize(value).map_err(|e| FieldError {
+            field: e.path().to_string(),
+            message: e.inner().to_string(),
+        })
+    }
+}
+
+impl HotkeyConfig {
+    /// 按 (操作, 快捷键, 范围) 列出所有非空绑定
+    pub fn entries(&self) -> Vec<(AppAction, &str, HotkeyScope)> {
+        self.bindings
+            .iter()
+            .filter(|(_, binding)| !binding.shortcut.trim().is_empty())
+            .map(|(action, binding)| (*action, binding.shortcut.as_str(), binding.scope))
+            .collect()
+    }
+
+    pub fn shortcut_for(&self, action: AppAction) -> Option<&str> {
+        self.bindings
+            .get(&action)
+            .map(|binding| binding.shortcut.as_str())
+            .filter(|shortcut| !shortcut.trim().is_empty())
+    }
+}
+
 impl WindowSize {
-    pub fn get_preset_sizes() -> Vec<(&'static str, u32, u32)> {
+    pub fn get_preset_sizes() -> Vec<(RatioPreset, u32, u32)> {
         vec![
-            ("phone", 320, 568),     // 9:16 手机比例
-            ("square", 400, 400),    // 1:1 正方形
-            ("golden", 400, 250),    // 16:10 黄金比例
-            ("widescreen", 480, 270), // 16:9 宽屏比例
+            (RatioPreset::Phone, 320, 568),
+            (RatioPreset::Square, 400, 400),
+            (RatioPreset::Golden, 400, 250),
+            (RatioPreset::Widescreen, 480, 270),
         ]
     }
 
-    pub fn apply_preset(&mut self, preset: &str) {
+    pub fn apply_preset(&mut self, preset: RatioPreset) {
         let presets = Self::get_preset_sizes();
         if let Some((_, width, height)) = presets.iter().find(|(name, _, _)| *name == preset) {
             self.width = *width;
             self.height = *height;
-            self.ratio_preset = preset.to_string();
         }
+        self.ratio_preset = preset;
     }
 
     pub fn get_aspect_ratio(&self) -> f64 {
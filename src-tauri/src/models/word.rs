@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Word {
     pub id: String,
     pub word: String,
@@ -20,12 +20,45 @@ pub struct Word {
     pub note: String,  // 技术说明或注释
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Example {
     pub source: String,
     pub trans: String,
 }
 
+/// 新建或编辑单词时前端提交的内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordInput {
+    pub word: String,
+    pub trans: String,
+    #[serde(default)]
+    pub phonetic: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub examples: Vec<Example>,
+    pub difficulty: Option<u8>,
+    #[serde(default)]
+    pub note: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateReason {
+    SameHeadword,       // 规范化后的单词相同
+    SimilarTranslation, // 释义高度相似
+    SharedExamples,     // 存在相同的例句
+}
+
+/// 一组疑似重复的单词及建议的合并方式
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    pub primary_id: String, // 建议保留的单词（学习进度最好的一个）
+    pub words: Vec<Word>,
+    pub reasons: Vec<DuplicateReason>,
+    pub score: f64, // 0-1，越高越可能重复
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WordProgress {
     pub word_id: String,
@@ -38,6 +71,34 @@ pub struct WordProgress {
     pub total_time_spent: u64, // 以秒为单位
 }
 
+impl WordProgress {
+    /// 答错次数占比，没有答题记录时为 0
+    pub fn error_rate(&self) -> f64 {
+        let answered = self.correct_count + self.incorrect_count;
+        if answered > 0 {
+            self.incorrect_count as f64 / answered as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// 是否到了复习时间，间隔与 `Word::get_next_review_time` 相同
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        now >= self.last_review + review_interval(self.mastery_level)
+    }
+}
+
+// 根据掌握程度决定复习间隔
+fn review_interval(mastery_level: u8) -> chrono::Duration {
+    match mastery_level {
+        0..=20 => chrono::Duration::hours(1),
+        21..=40 => chrono::Duration::hours(4),
+        41..=60 => chrono::Duration::hours(12),
+        61..=80 => chrono::Duration::days(1),
+        _ => chrono::Duration::days(3),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LearningStats {
     pub total_words: u32,
@@ -64,6 +125,40 @@ pub struct WordFilter {
     pub search_text: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WordSortKey {
+    Alphabetical,
+    CreatedAt,
+    Mastery,
+    NextDue,
+    Difficulty,
+    ErrorRate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordPageRequest {
+    pub filter: Option<WordFilter>,
+    pub sort_by: Option<WordSortKey>,
+    pub order: Option<SortOrder>,
+    pub cursor: Option<String>, // 上一页最后一个单词的ID
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordPage {
+    pub items: Vec<Word>,
+    pub total: u32,                  // 过滤后的总数，用于前端虚拟列表
+    pub next_cursor: Option<String>, // 没有下一页时为 None
+}
+
 impl Default for Word {
     fn default() -> Self {
         let now = Utc::now();
@@ -107,6 +202,14 @@ impl Word {
         }
     }
 
+    /// 用学习进度存储中的记录覆盖进度字段，单词本文件不保存这些字段
+    pub fn apply_progress(&mut self, progress: &WordProgress) {
+        self.progress = progress.progress;
+        self.mastery_level = progress.mastery_level;
+        self.review_count = progress.review_count;
+        self.last_review = Some(progress.last_review);
+    }
+
     pub fn update_progress(&mut self, new_progress: u8, is_correct: bool) {
         self.progress = new_progress;
         self.last_review = Some(Utc::now());
@@ -143,15 +246,7 @@ impl Word {
     }
 
     pub fn get_next_review_time(&self) -> Option<DateTime<Utc>> {
-        self.last_review.map(|last_review| {
-            let review_interval = match self.mastery_level {
-                0..=20 => chrono::Duration::hours(1),
-                21..=40 => chrono::Duration::hours(4),
-                41..=60 => chrono::Duration::hours(12),
-                61..=80 => chrono::Duration::days(1),
-                _ => chrono::Duration::days(3),
-            };
-            last_review + review_interval
-        })
+        self.last_review
+            .map(|last_review| last_review + review_interval(self.mastery_level))
     }
 } 
\ No newline at end of file
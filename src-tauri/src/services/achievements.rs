@@ -0,0 +1,257 @@
+use crate::models::{Achievement, AchievementCategory, Word, WordProgress};
+use crate::services::{progress, xml_parser};
+use crate::utils::{config, stores};
+use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate, Timelike, Utc};
+use once_cell::sync::Lazy;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+
+pub const ACHIEVEMENTS_STORE: &str = "achievements.json";
+
+// 复习和会话可能同时触发检查，依次执行避免同一成就解锁两次
+static EVALUATION: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+const UNLOCKED_KEY: &str = "unlocked";     // 成就 id -> 解锁时间
+const STUDY_DAYS_KEY: &str = "study_days"; // 有复习记录的本地日期，用于计算连续学习天数
+const MAX_STUDY_DAYS: usize = 400;
+
+// 与学习统计一致：掌握度达到 80 视为已掌握
+const MASTERED_LEVEL: u8 = 80;
+// 满分学习至少要回答的单词数，避免答一两个就解锁
+const PERFECT_SESSION_MIN_ANSWERS: u32 = 10;
+// 标签中至少有这么多单词才算一个可完成的标签
+const MIN_TAG_WORDS: usize = 5;
+
+/// 触发成就检查的事件
+#[derive(Debug, Clone, Copy)]
+pub enum Trigger {
+    Review { at: DateTime<Utc> },
+    Session { correct: u32, total: u32 },
+}
+
+enum Condition {
+    Streak(u32),
+    Mastered(u32),
+    PerfectSession,
+    NightOwl,  // 00:00-05:00 复习
+    EarlyBird, // 05:00-07:00 复习
+}
+
+struct Rule {
+    id: &'static str,
+    category: AchievementCategory,
+    title: &'static str,
+    description: &'static str,
+    condition: Condition,
+}
+
+const RULES: &[Rule] = &[
+    Rule { id: "streak_3", category: AchievementCategory::Streak, title: "初见成效", description: "连续学习 3 天", condition: Condition::Streak(3) },
+    Rule { id: "streak_7", category: AchievementCategory::Streak, title: "坚持一周", description: "连续学习 7 天", condition: Condition::Streak(7) },
+    Rule { id: "streak_30", category: AchievementCategory::Streak, title: "月度学霸", description: "连续学习 30 天", condition: Condition::Streak(30) },
+    Rule { id: "streak_100", category: AchievementCategory::Streak, title: "百日筑基", description: "连续学习 100 天", condition: Condition::Streak(100) },
+    Rule { id: "mastered_10", category: AchievementCategory::Mastery, title: "小有积累", description: "掌握 10 个单词", condition: Condition::Mastered(10) },
+    Rule { id: "mastered_50", category: AchievementCategory::Mastery, title: "词汇新秀", description: "掌握 50 个单词", condition: Condition::Mastered(50) },
+    Rule { id: "mastered_100", category: AchievementCategory::Mastery, title: "百词斩", description: "掌握 100 个单词", condition: Condition::Mastered(100) },
+    Rule { id: "mastered_500", category: AchievementCategory::Mastery, title: "词汇达人", description: "掌握 500 个单词", condition: Condition::Mastered(500) },
+    Rule { id: "perfect_session", category: AchievementCategory::Session, title: "全对", description: "一次学习中答对至少 10 个单词且没有答错", condition: Condition::PerfectSession },
+    Rule { id: "night_owl", category: AchievementCategory::Schedule, title: "夜猫子", description: "在凌晨 0 点到 5 点之间复习单词", condition: Condition::NightOwl },
+    Rule { id: "early_bird", category: AchievementCategory::Schedule, title: "早起鸟", description: "在早上 5 点到 7 点之间复习单词", condition: Condition::EarlyBird },
+];
+
+struct Context {
+    trigger: Trigger,
+    streak: u32,
+    mastered: u32,
+}
+
+impl Condition {
+    fn is_met(&self, context: &Context) -> bool {
+        match (self, context.trigger) {
+            (Condition::Streak(days), _) => context.streak >= *days,
+            (Condition::Mastered(count), _) => context.mastered >= *count,
+            (Condition::PerfectSession, Trigger::Session { correct, total }) => {
+                total >= PERFECT_SESSION_MIN_ANSWERS && correct == total
+            }
+            (Condition::NightOwl, Trigger::Review { at }) => at.with_timezone(&Local).hour() < 5,
+            (Condition::EarlyBird, Trigger::Review { at }) => (5..7).contains(&at.with_timezone(&Local).hour()),
+            _ => false,
+        }
+    }
+}
+
+impl Rule {
+    fn achievement(&self, unlocked_at: Option<DateTime<Utc>>) -> Achievement {
+        Achievement {
+            id: self.id.to_string(),
+            category: self.category,
+            title: self.title.to_string(),
+            description: self.description.to_string(),
+            unlocked_at,
+        }
+    }
+}
+
+fn tag_achievement(tag: &str, unlocked_at: Option<DateTime<Utc>>) -> Achievement {
+    Achievement {
+        id: format!("tag:{}", tag),
+        category: AchievementCategory::Tag,
+        title: format!("攻克「{}」", tag),
+        description: format!("掌握标签「{}」中的所有单词", tag),
+        unlocked_at,
+    }
+}
+
+/// 复习后检查成就，在后台执行，不影响评分
+pub fn spawn_evaluate(app: &AppHandle, trigger: Trigger) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = evaluate(&app, trigger).await {
+            log::warn!("检查成就失败: {}", e);
+        }
+    });
+}
+
+/// 按规则检查成就，保存并通知新解锁的成就
+pub async fn evaluate(app: &AppHandle, trigger: Trigger) -> Result<Vec<Achievement>> {
+    let _guard = EVALUATION.lock().await;
+    let store = stores::open(app, ACHIEVEMENTS_STORE)?;
+    let mut unlocked = load_unlocked(app)?;
+
+    let mut study_days: BTreeSet<NaiveDate> = store
+        .get(STUDY_DAYS_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default();
+    if let Trigger::Review { at } = trigger {
+        if study_days.insert(at.with_timezone(&Local).date_naive()) {
+            while study_days.len() > MAX_STUDY_DAYS {
+                study_days.pop_first();
+            }
+            store.set(STUDY_DAYS_KEY, serde_json::to_value(&study_days)?);
+        }
+    }
+
+    let progresses = progress::load_all(app)?;
+    let context = Context {
+        trigger,
+        streak: streak(&study_days, Local::now().date_naive()),
+        mastered: progresses.iter().filter(|p| p.mastery_level >= MASTERED_LEVEL).count() as u32,
+    };
+
+    let now = Utc::now();
+    let mut new_unlocks: Vec<Achievement> = RULES
+        .iter()
+        .filter(|rule| !unlocked.contains_key(rule.id) && rule.condition.is_met(&context))
+        .map(|rule| rule.achievement(Some(now)))
+        .collect();
+
+    if let Trigger::Review { .. } = trigger {
+        let words = xml_parser::load_words(None).await?;
+        new_unlocks.extend(
+            completed_tags(&words, &progresses)
+                .into_iter()
+                .map(|tag| tag_achievement(&tag, Some(now)))
+                .filter(|achievement| !unlocked.contains_key(&achievement.id)),
+        );
+    }
+
+    if new_unlocks.is_empty() {
+        stores::save(app, ACHIEVEMENTS_STORE)?;
+        return Ok(new_unlocks);
+    }
+
+    for achievement in &new_unlocks {
+        unlocked.insert(achievement.id.clone(), now);
+    }
+    store.set(UNLOCKED_KEY, serde_json::to_value(&unlocked)?);
+    stores::save(app, ACHIEVEMENTS_STORE)?;
+
+    let settings = config::load_settings().await?;
+    let notify = settings.notification.enable_notifications && settings.notification.achievement_notifications;
+    for achievement in &new_unlocks {
+        log::info!("🏆 解锁成就: {} ({})", achievement.title, achievement.id);
+
+        if let Err(e) = app.emit("achievement-unlocked", achievement) {
+            log::warn!("发送成就事件失败: {}", e);
+        }
+        if notify {
+            let result = app
+                .notification()
+                .builder()
+                .title("🏆 解锁成就")
+                .body(format!("{}：{}", achievement.title, achievement.description))
+                .show();
+            if let Err(e) = result {
+                log::warn!("发送成就通知失败: {}", e);
+            }
+        }
+    }
+
+    Ok(new_unlocks)
+}
+
+/// 所有成就及解锁状态：固定规则全部列出，标签成就只列出已解锁的
+pub fn list(app: &AppHandle) -> Result<Vec<Achievement>> {
+    let unlocked = load_unlocked(app)?;
+
+    let mut achievements: Vec<Achievement> = RULES
+        .iter()
+        .map(|rule| rule.achievement(unlocked.get(rule.id).copied()))
+        .collect();
+    achievements.extend(
+        unlocked
+            .iter()
+            .filter_map(|(id, at)| id.strip_prefix("tag:").map(|tag| tag_achievement(tag, Some(*at)))),
+    );
+
+    Ok(achievements)
+}
+
+fn load_unlocked(app: &AppHandle) -> Result<BTreeMap<String, DateTime<Utc>>> {
+    let store = stores::open(app, ACHIEVEMENTS_STORE)?;
+    Ok(store
+        .get(UNLOCKED_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default())
+}
+
+/// 截至今天的连续学习天数，今天还没学习时从昨天开始算
+fn streak(days: &BTreeSet<NaiveDate>, today: NaiveDate) -> u32 {
+    let mut day = if days.contains(&today) {
+        today
+    } else {
+        today - chrono::Duration::days(1)
+    };
+
+    let mut count = 0;
+    while days.contains(&day) {
+        count += 1;
+        day -= chrono::Duration::days(1);
+    }
+    count
+}
+
+/// 所有单词都已掌握的标签
+fn completed_tags(words: &[Word], progresses: &[WordProgress]) -> Vec<String> {
+    let mastery: HashMap<&str, u8> = progresses
+        .iter()
+        .map(|p| (p.word_id.as_str(), p.mastery_level))
+        .collect();
+
+    let mut tags: BTreeMap<&str, (usize, bool)> = BTreeMap::new();
+    for word in words {
+        let mastered = mastery.get(word.id.as_str()).is_some_and(|level| *level >= MASTERED_LEVEL);
+        for tag in &word.tags {
+            let entry = tags.entry(tag.as_str()).or_insert((0, true));
+            entry.0 += 1;
+            entry.1 &= mastered;
+        }
+    }
+
+    tags.into_iter()
+        .filter(|(_, (count, all_mastered))| *count >= MIN_TAG_WORDS && *all_mastered)
+        .map(|(tag, _)| tag.to_string())
+        .collect()
+}
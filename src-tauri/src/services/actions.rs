@@ -0,0 +1,105 @@
+use crate::models::{AppAction, HotkeyConfig, HotkeyScope};
+use crate::services::{auto_switch, current_word, reminders, settings};
+use crate::services::window::MAIN_WINDOW;
+use crate::utils::config;
+use serde::{Deserialize, Serialize};
+use tauri::menu::MenuItem;
+use tauri::{AppHandle, Emitter, Manager, Wry};
+
+/// 操作及其当前绑定，供设置界面和前端窗口内快捷键使用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionBinding {
+    pub action: AppAction,
+    pub label: String,
+    pub shortcut: Option<String>,
+    pub scope: HotkeyScope,
+}
+
+/// 按 `AppAction::ALL` 的顺序列出所有操作的绑定，未绑定的操作 `shortcut` 为 None
+pub fn list_bindings(config: &HotkeyConfig) -> Vec<ActionBinding> {
+    AppAction::ALL
+        .into_iter()
+        .map(|action| ActionBinding {
+            action,
+            label: action.label().to_string(),
+            shortcut: config.shortcut_for(action).map(|s| s.to_string()),
+            scope: config.bindings.get(&action).map(|b| b.scope).unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// 创建触发指定操作的菜单项，菜单项 id 即操作 id，由应用菜单事件统一分发
+pub fn menu_item(app: &AppHandle, action: AppAction) -> tauri::Result<MenuItem<Wry>> {
+    MenuItem::with_id(app, action.id(), action.label(), true, None::<&str>)
+}
+
+/// 执行操作，快捷键、菜单、托盘和前端按键都经由这里分发
+///
+/// 切换单词、窗口和设置相关的操作在后端完成，其余操作通过 `action-triggered` 事件交给前端。
+pub fn trigger(app: &AppHandle, action: AppAction) {
+    log::debug!("🎬 触发操作: {:?}", action);
+
+    let Some(window) = app.get_webview_window(MAIN_WINDOW) else {
+        return;
+    };
+
+    match action {
+        AppAction::ToggleWindow => {
+            if window.is_visible().unwrap_or(false) {
+                let _ = window.hide();
+            } else {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }
+        AppAction::NextWord | AppAction::PrevWord | AppAction::ToggleAutoSwitch => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let result = match action {
+                    AppAction::NextWord => current_word::next(&app).await.map(|_| ()),
+                    AppAction::PrevWord => current_word::previous(&app).await.map(|_| ()),
+                    _ => auto_switch::toggle(&app).await,
+                };
+                if let Err(e) = result {
+                    log::warn!("执行操作失败 {:?}: {}", action, e);
+                }
+            });
+        }
+        AppAction::ToggleAlwaysOnTop => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = toggle_always_on_top(&app).await {
+                    log::warn!("切换窗口置顶失败: {}", e);
+                }
+            });
+        }
+        AppAction::StudyNow => {
+            let _ = window.show();
+            let _ = window.set_focus();
+            reminders::dismiss(app);
+            emit(&window, action);
+        }
+        AppAction::OpenSearch => {
+            let _ = window.show();
+            let _ = window.set_focus();
+            emit(&window, action);
+        }
+        _ => emit(&window, action),
+    }
+}
+
+async fn toggle_always_on_top(app: &AppHandle) -> anyhow::Result<()> {
+    let current = config::load_settings().await?;
+    let patch = serde_json::json!({ "always_on_top": !current.always_on_top });
+
+    settings::apply_patch(app, patch)
+        .await
+        .map(|_| ())
+        .map_err(|errors| anyhow::anyhow!("{:?}", errors))
+}
+
+fn emit(window: &tauri::WebviewWindow, action: AppAction) {
+    if let Err(e) = window.emit("action-triggered", action) {
+        log::warn!("发送操作事件失败 {:?}: {}", action, e);
+    }
+}
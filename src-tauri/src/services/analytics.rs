@@ -0,0 +1,196 @@
+use crate::models::{
+    DailyReviewCount, DateRange, HardWord, HourlyPerformance, RetentionBucket, ReviewRecord, TagPerformance,
+};
+use crate::services::{review_log, xml_parser};
+use anyhow::Result;
+use chrono::{Local, NaiveDate, Timelike};
+use std::collections::{BTreeMap, HashMap};
+use tauri::AppHandle;
+
+// 复习间隔分组（天），最后一组不设上限
+const RETENTION_BUCKETS: [(&str, u32, Option<u32>); 6] = [
+    ("1 天内", 0, Some(1)),
+    ("1-3 天", 1, Some(3)),
+    ("3-7 天", 3, Some(7)),
+    ("7-14 天", 7, Some(14)),
+    ("14-30 天", 14, Some(30)),
+    ("30 天以上", 30, None),
+];
+
+// 复习次数太少的单词正确率没有参考价值，不参与最难单词排名
+const MIN_REVIEWS_FOR_HARD_WORD: u32 = 2;
+
+/// 答题数、答对数和总用时，各项分析共用
+#[derive(Default)]
+struct Tally {
+    reviews: u32,
+    correct: u32,
+    time_spent: u64,
+}
+
+impl Tally {
+    fn add(&mut self, record: &ReviewRecord) {
+        self.reviews += 1;
+        self.correct += record.is_correct as u32;
+        self.time_spent += record.time_spent;
+    }
+
+    fn accuracy(&self) -> f64 {
+        ratio(self.correct as f64, self.reviews)
+    }
+
+    fn average_time_spent(&self) -> f64 {
+        ratio(self.time_spent as f64, self.reviews)
+    }
+}
+
+fn ratio(value: f64, count: u32) -> f64 {
+    if count == 0 {
+        0.0
+    } else {
+        value / count as f64
+    }
+}
+
+/// 按距离上次复习的间隔统计记忆保持率；首次学习没有间隔，不计入
+pub fn retention_curve(app: &AppHandle, range: DateRange) -> Result<Vec<RetentionBucket>> {
+    let records = review_log::load(app, range)?;
+
+    let mut tallies: Vec<Tally> = RETENTION_BUCKETS.iter().map(|_| Tally::default()).collect();
+    for record in &records {
+        let Some(previous) = record.previous_review else {
+            continue;
+        };
+        let days = (record.reviewed_at - previous).num_days().max(0) as u32;
+        let bucket = RETENTION_BUCKETS
+            .iter()
+            .position(|(_, min, max)| days >= *min && !max.is_some_and(|max| days >= max));
+        if let Some(index) = bucket {
+            tallies[index].add(record);
+        }
+    }
+
+    Ok(RETENTION_BUCKETS
+        .iter()
+        .zip(tallies)
+        .map(|((label, min_days, max_days), tally)| RetentionBucket {
+            label: label.to_string(),
+            min_days: *min_days,
+            max_days: *max_days,
+            reviews: tally.reviews,
+            recalled: tally.correct,
+            retention: tally.accuracy(),
+        })
+        .collect())
+}
+
+/// 各标签的正确率和平均用时，按复习次数从多到少排列；没有标签的单词不计入
+pub async fn tag_performance(app: &AppHandle, range: DateRange) -> Result<Vec<TagPerformance>> {
+    let records = review_log::load(app, range)?;
+    let words = xml_parser::load_words(None).await?;
+    let tags_by_word: HashMap<&str, &[String]> = words
+        .iter()
+        .map(|word| (word.id.as_str(), word.tags.as_slice()))
+        .collect();
+
+    let mut tallies: BTreeMap<&str, Tally> = BTreeMap::new();
+    for record in &records {
+        for tag in tags_by_word.get(record.word_id.as_str()).copied().unwrap_or_default() {
+            tallies.entry(tag.as_str()).or_default().add(record);
+        }
+    }
+
+    let mut performance: Vec<TagPerformance> = tallies
+        .into_iter()
+        .map(|(tag, tally)| TagPerformance {
+            tag: tag.to_string(),
+            reviews: tally.reviews,
+            correct: tally.correct,
+            accuracy: tally.accuracy(),
+            average_time_spent: tally.average_time_spent(),
+        })
+        .collect();
+    performance.sort_by(|a, b| b.reviews.cmp(&a.reviews));
+
+    Ok(performance)
+}
+
+/// 正确率最低的单词，正确率相同时答错次数多的在前
+pub async fn hardest_words(app: &AppHandle, range: DateRange, limit: usize) -> Result<Vec<HardWord>> {
+    let records = review_log::load(app, range)?;
+    let words = xml_parser::load_words(None).await?;
+    let headwords: HashMap<&str, &str> = words
+        .iter()
+        .map(|word| (word.id.as_str(), word.word.as_str()))
+        .collect();
+
+    let mut tallies: HashMap<&str, Tally> = HashMap::new();
+    for record in &records {
+        tallies.entry(record.word_id.as_str()).or_default().add(record);
+    }
+
+    let mut hard_words: Vec<HardWord> = tallies
+        .into_iter()
+        .filter(|(_, tally)| tally.reviews >= MIN_REVIEWS_FOR_HARD_WORD && tally.correct < tally.reviews)
+        .map(|(word_id, tally)| HardWord {
+            word_id: word_id.to_string(),
+            word: headwords.get(word_id).copied().unwrap_or_default().to_string(),
+            reviews: tally.reviews,
+            incorrect: tally.reviews - tally.correct,
+            accuracy: tally.accuracy(),
+            average_time_spent: tally.average_time_spent(),
+        })
+        .collect();
+    hard_words.sort_by(|a, b| {
+        a.accuracy
+            .total_cmp(&b.accuracy)
+            .then_with(|| b.incorrect.cmp(&a.incorrect))
+            .then_with(|| a.word_id.cmp(&b.word_id))
+    });
+    hard_words.truncate(limit);
+
+    Ok(hard_words)
+}
+
+/// 一天中每个小时（本地时间）的答题情况，始终返回 24 项
+pub fn hourly_performance(app: &AppHandle, range: DateRange) -> Result<Vec<HourlyPerformance>> {
+    let records = review_log::load(app, range)?;
+
+    let mut tallies: Vec<Tally> = (0..24).map(|_| Tally::default()).collect();
+    for record in &records {
+        let hour = record.reviewed_at.with_timezone(&Local).hour() as usize;
+        tallies[hour].add(record);
+    }
+
+    Ok(tallies
+        .into_iter()
+        .enumerate()
+        .map(|(hour, tally)| HourlyPerformance {
+            hour: hour as u32,
+            reviews: tally.reviews,
+            correct: tally.correct,
+            accuracy: tally.accuracy(),
+            average_time_spent: tally.average_time_spent(),
+        })
+        .collect())
+}
+
+/// 每天的复习次数（本地日期），只返回有复习的日期，按日期先后排列
+pub fn review_heatmap(app: &AppHandle, range: DateRange) -> Result<Vec<DailyReviewCount>> {
+    let records = review_log::load(app, range)?;
+
+    let mut tallies: BTreeMap<NaiveDate, Tally> = BTreeMap::new();
+    for record in &records {
+        let date = record.reviewed_at.with_timezone(&Local).date_naive();
+        tallies.entry(date).or_default().add(record);
+    }
+
+    Ok(tallies
+        .into_iter()
+        .map(|(date, tally)| DailyReviewCount {
+            date,
+            reviews: tally.reviews,
+            correct: tally.correct,
+        })
+        .collect())
+}
@@ -0,0 +1,294 @@
+use crate::services::settings::{self, SettingsChange, SettingsSection};
+use crate::services::xml_parser;
+use anyhow::{Result, anyhow};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+use once_cell::sync::Lazy;
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::AppHandle;
+
+// 支持的发音文件格式，同名文件按此顺序优先
+pub const AUDIO_EXTENSIONS: [&str; 3] = ["mp3", "ogg", "wav"];
+
+// 播放线程检查播放是否结束的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// 单词到发音文件的查找结果缓存，None 表示没有找到文件
+static CACHE: Lazy<Mutex<HashMap<String, Option<PathBuf>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static PLAYER: Lazy<Mutex<Option<Player>>> = Lazy::new(|| Mutex::new(None));
+
+/// 导入发音包的结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AudioImportReport {
+    pub imported: u32,
+    pub skipped: u32,   // 不支持的格式
+    pub unmatched: u32, // 当前单词本中没有对应单词（仍会导入，供其他单词本使用）
+}
+
+enum Command {
+    Play {
+        path: PathBuf,
+        volume: f32,
+        reply: Sender<Result<()>>,
+    },
+    Stop,
+}
+
+// rodio 的输出流不能跨线程传递，由专门的播放线程持有
+struct Player {
+    sender: Sender<Command>,
+    playing: Arc<AtomicBool>,
+}
+
+/// 切换单词本后清空查找缓存
+pub fn register_settings_subscriber() {
+    settings::subscribe(on_settings_changed);
+}
+
+fn on_settings_changed(_app: &AppHandle, change: &SettingsChange) {
+    if change.touches(SettingsSection::ActiveWordbook) {
+        clear_cache();
+    }
+}
+
+pub fn clear_cache() {
+    CACHE.lock().unwrap().clear();
+}
+
+/// 查找单词的发音文件：先找当前单词本旁的 audio/<单词本名> 目录，再找导入的发音包
+pub fn find_audio_file(headword: &str) -> Option<PathBuf> {
+    let key = file_key(headword);
+    if key.is_empty() {
+        return None;
+    }
+
+    if let Some(cached) = CACHE.lock().unwrap().get(&key) {
+        return cached.clone();
+    }
+
+    let found = audio_directories()
+        .into_iter()
+        .flat_map(|dir| AUDIO_EXTENSIONS.iter().map(move |ext| dir.join(format!("{}.{}", key, ext))))
+        .find(|path| path.is_file());
+
+    CACHE.lock().unwrap().insert(key, found.clone());
+    found
+}
+
+/// 打断当前播放并播放指定文件，解码成功后返回
+pub fn play_file(path: &Path, volume: f32) -> Result<()> {
+    let (reply, result) = crossbeam_channel::bounded(1);
+    let command = Command::Play {
+        path: path.to_path_buf(),
+        volume,
+        reply,
+    };
+
+    with_player(|player| player.sender.send(command).map_err(|_| anyhow!("播放线程已退出")))?;
+
+    result
+        .recv()
+        .map_err(|_| anyhow!("播放线程已退出"))?
+}
+
+pub fn stop() {
+    if let Some(player) = PLAYER.lock().unwrap().as_ref() {
+        let _ = player.sender.send(Command::Stop);
+    }
+}
+
+pub fn is_playing() -> bool {
+    PLAYER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|player| player.playing.load(Ordering::SeqCst))
+}
+
+/// 导入发音包：目录中以单词命名的音频文件（如 `apple.mp3`）复制到发音包目录，同名文件覆盖
+pub async fn import_pack(source: &Path) -> Result<AudioImportReport> {
+    if !source.is_dir() {
+        return Err(anyhow!("发音包目录不存在: {:?}", source));
+    }
+
+    let target = pack_directory()?;
+    fs::create_dir_all(&target)?;
+
+    let headwords: HashSet<String> = xml_parser::load_words(None)
+        .await?
+        .iter()
+        .map(|w| file_key(&w.word))
+        .collect();
+
+    let mut report = AudioImportReport::default();
+    for entry in fs::read_dir(source)? {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .filter(|ext| AUDIO_EXTENSIONS.contains(&ext.as_str()));
+        let stem = path.file_stem().and_then(|s| s.to_str()).map(file_key);
+
+        let (Some(extension), Some(key)) = (extension, stem) else {
+            report.skipped += 1;
+            continue;
+        };
+        if key.is_empty() {
+            report.skipped += 1;
+            continue;
+        }
+
+        // 同一单词只保留一种格式，避免旧格式的文件优先于新导入的文件
+        for ext in AUDIO_EXTENSIONS.iter().filter(|ext| **ext != extension) {
+            let _ = fs::remove_file(target.join(format!("{}.{}", key, ext)));
+        }
+        fs::copy(&path, target.join(format!("{}.{}", key, extension)))?;
+
+        report.imported += 1;
+        if !headwords.contains(&key) {
+            report.unmatched += 1;
+        }
+    }
+
+    clear_cache();
+    log::info!(
+        "🎵 发音包导入完成: 导入 {} 个，跳过 {} 个，{} 个不在当前单词本中",
+        report.imported, report.skipped, report.unmatched
+    );
+    Ok(report)
+}
+
+/// 导入的发音包统一存放的目录
+pub fn pack_directory() -> Result<PathBuf> {
+    xml_parser::get_vocabulary_directory().map(|dir| dir.join("audio_packs"))
+}
+
+fn audio_directories() -> Vec<PathBuf> {
+    let mut directories = Vec::new();
+
+    if let Ok(vocabulary) = xml_parser::get_vocabulary_directory() {
+        let wordbook = xml_parser::active_wordbook();
+        let stem = Path::new(&wordbook)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or(&wordbook)
+            .to_string();
+        directories.push(vocabulary.join("audio").join(stem));
+    }
+    if let Ok(packs) = pack_directory() {
+        directories.push(packs);
+    }
+
+    directories
+}
+
+/// 单词对应的文件名（不含扩展名）：小写，空白换成下划线，去掉文件名中不允许的字符
+pub fn file_key(headword: &str) -> String {
+    headword
+        .trim()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("_")
+        .chars()
+        .filter(|c| !matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|'))
+        .collect()
+}
+
+fn with_player<T>(f: impl FnOnce(&Player) -> Result<T>) -> Result<T> {
+    let mut guard = PLAYER.lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(start_player()?);
+    }
+    f(guard.as_ref().unwrap())
+}
+
+fn start_player() -> Result<Player> {
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let (ready_sender, ready) = crossbeam_channel::bounded(1);
+    let playing = Arc::new(AtomicBool::new(false));
+
+    let thread_playing = playing.clone();
+    std::thread::Builder::new()
+        .name("audio-player".to_string())
+        .spawn(move || run_player(receiver, ready_sender, thread_playing))?;
+
+    ready
+        .recv()
+        .map_err(|_| anyhow!("播放线程启动失败"))??;
+
+    log::info!("🎵 音频播放线程已启动");
+    Ok(Player { sender, playing })
+}
+
+fn run_player(receiver: Receiver<Command>, ready: Sender<Result<()>>, playing: Arc<AtomicBool>) {
+    let (_stream, handle) = match OutputStream::try_default() {
+        Ok(output) => output,
+        Err(e) => {
+            let _ = ready.send(Err(anyhow!("无法打开音频输出设备: {}", e)));
+            return;
+        }
+    };
+    let _ = ready.send(Ok(()));
+
+    let mut sink: Option<Sink> = None;
+    loop {
+        match receiver.recv_timeout(POLL_INTERVAL) {
+            Ok(Command::Play { path, volume, reply }) => {
+                if let Some(old) = sink.take() {
+                    old.stop();
+                }
+
+                let result = open_sink(&handle, &path, volume);
+
+                match result {
+                    Ok(new_sink) => {
+                        sink = Some(new_sink);
+                        playing.store(true, Ordering::SeqCst);
+                        let _ = reply.send(Ok(()));
+                    }
+                    Err(e) => {
+                        playing.store(false, Ordering::SeqCst);
+                        let _ = reply.send(Err(e));
+                    }
+                }
+            }
+            Ok(Command::Stop) => {
+                if let Some(old) = sink.take() {
+                    old.stop();
+                }
+                playing.store(false, Ordering::SeqCst);
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if sink.as_ref().is_some_and(|s| s.empty()) {
+                    sink = None;
+                    playing.store(false, Ordering::SeqCst);
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+}
+
+fn open_sink(handle: &OutputStreamHandle, path: &Path, volume: f32) -> Result<Sink> {
+    let file = fs::File::open(path)?;
+    let source = Decoder::new(BufReader::new(file))
+        .map_err(|e| anyhow!("无法解码音频文件 {:?}: {}", path, e))?;
+
+    let sink = Sink::try_new(handle)?;
+    sink.set_volume(volume);
+    sink.append(source);
+    Ok(sink)
+}
@@ -0,0 +1,209 @@
+use crate::models::{PausePolicy, Settings};
+use crate::services::current_word;
+use crate::services::speech_queue::{self, SpeechContent};
+use crate::services::settings::{self, SettingsChange, SettingsSection};
+use crate::utils::{activity, config};
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+const TICK: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseReason {
+    Idle,       // 用户离开
+    Fullscreen, // 全屏程序运行中
+}
+
+/// 自动切换的当前状态，变化时通过 `auto-switch-state` 事件发送给前端
+#[derive(Debug, Clone, Serialize)]
+pub struct AutoSwitchState {
+    pub enabled: bool,
+    pub interval: u32,
+    pub paused: Option<PauseReason>,
+    pub remaining: u32, // 距离下一次切换的秒数
+}
+
+struct Ticker {
+    enabled: bool,
+    interval: u32,
+    policy: PausePolicy,
+    speak: bool, // 切换后按朗读顺序朗读新单词
+    elapsed: u32,
+    paused: Option<PauseReason>,
+}
+
+impl Ticker {
+    fn state(&self) -> AutoSwitchState {
+        AutoSwitchState {
+            enabled: self.enabled,
+            interval: self.interval,
+            paused: self.paused,
+            remaining: self.interval.saturating_sub(self.elapsed),
+        }
+    }
+
+    fn apply(&mut self, settings: &Settings) {
+        self.enabled = settings.auto_switch;
+        self.interval = settings.switch_interval.max(1);
+        self.policy = settings.pause_policy.clone();
+        self.speak = settings.enable_tts && settings.tts.speak_on_switch;
+        self.elapsed = 0;
+    }
+}
+
+static TICKER: Lazy<Mutex<Ticker>> = Lazy::new(|| {
+    Mutex::new(Ticker {
+        enabled: false,
+        interval: 7,
+        policy: PausePolicy::default(),
+        speak: false,
+        elapsed: 0,
+        paused: None,
+    })
+});
+
+/// 启动后台计时任务，窗口隐藏时同样按设置的间隔切换单词
+pub async fn start(app: &AppHandle) -> Result<()> {
+    let current = config::load_settings().await?;
+    TICKER.lock().unwrap().apply(&current);
+    settings::subscribe(on_settings_changed);
+
+    // 启动时先显示一个单词，托盘和窗口都有内容可显示
+    if current_word::get().is_none() {
+        if let Err(e) = current_word::next(app).await {
+            log::warn!("加载初始单词失败: {}", e);
+        }
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut ticks = tokio::time::interval(TICK);
+        loop {
+            ticks.tick().await;
+            if tick(&app) {
+                match current_word::next(&app).await {
+                    Ok(Some(word)) if TICKER.lock().unwrap().speak => {
+                        speech_queue::play(&app, SpeechContent::Sequence(word));
+                    }
+                    Ok(_) => {}
+                    Err(e) => log::warn!("自动切换单词失败: {}", e),
+                }
+            }
+        }
+    });
+
+    log::info!("⏱️ 自动切换计时已启动");
+    Ok(())
+}
+
+pub fn get_state() -> AutoSwitchState {
+    TICKER.lock().unwrap().state()
+}
+
+/// 手动切换单词后重新计时
+pub fn reset_countdown() {
+    TICKER.lock().unwrap().elapsed = 0;
+}
+
+/// 开启或关闭自动切换，写入设置后由设置订阅更新计时状态
+pub async fn set_enabled(app: &AppHandle, enabled: bool) -> Result<()> {
+    settings::apply_patch(app, serde_json::json!({ "auto_switch": enabled }))
+        .await
+        .map_err(|errors| anyhow::anyhow!("{:?}", errors))?;
+    Ok(())
+}
+
+pub async fn toggle(app: &AppHandle) -> Result<()> {
+    let enabled = TICKER.lock().unwrap().enabled;
+    set_enabled(app, !enabled).await
+}
+
+fn on_settings_changed(app: &AppHandle, change: &SettingsChange) {
+    let relevant = [
+        SettingsSection::AutoSwitch,
+        SettingsSection::SwitchInterval,
+        SettingsSection::PausePolicy,
+        SettingsSection::EnableTts,
+        SettingsSection::Tts,
+    ];
+    if !relevant.iter().any(|section| change.touches(*section)) {
+        return;
+    }
+
+    let state = {
+        let mut ticker = TICKER.lock().unwrap();
+        ticker.apply(&change.new);
+        ticker.state()
+    };
+    emit_state(app, &state);
+}
+
+/// 每秒调用一次，返回是否需要切换到下一个单词
+fn tick(app: &AppHandle) -> bool {
+    let (enabled, policy) = {
+        let ticker = TICKER.lock().unwrap();
+        (ticker.enabled, ticker.policy.clone())
+    };
+    if !enabled {
+        return false;
+    }
+
+    let paused = pause_reason(&policy);
+
+    let mut ticker = TICKER.lock().unwrap();
+    if ticker.paused != paused {
+        ticker.paused = paused;
+        let state = ticker.state();
+        drop(ticker);
+
+        match paused {
+            Some(reason) => log::info!("⏸️ 自动切换已暂停: {:?}", reason),
+            None => log::info!("▶️ 自动切换已恢复"),
+        }
+        emit_state(app, &state);
+        return false;
+    }
+
+    if ticker.paused.is_some() {
+        return false;
+    }
+
+    // 朗读还没结束时不计时，读完后再开始倒数
+    if ticker.policy.while_speaking && speech_queue::is_busy() {
+        return false;
+    }
+
+    ticker.elapsed += 1;
+    if ticker.elapsed >= ticker.interval {
+        ticker.elapsed = 0;
+        return true;
+    }
+    false
+}
+
+fn pause_reason(policy: &PausePolicy) -> Option<PauseReason> {
+    if policy.when_fullscreen && activity::is_fullscreen_app_running() {
+        return Some(PauseReason::Fullscreen);
+    }
+
+    if policy.when_idle {
+        if let Some(idle) = activity::idle_seconds() {
+            if idle >= policy.idle_seconds as u64 {
+                return Some(PauseReason::Idle);
+            }
+        }
+    }
+
+    None
+}
+
+fn emit_state(app: &AppHandle, state: &AutoSwitchState) {
+    if let Err(e) = app.emit("auto-switch-state", state) {
+        log::warn!("发送自动切换状态失败: {}", e);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::models::Word;
+use crate::services::{auto_switch, learning, tray, xml_parser};
+use anyhow::{Result, anyhow};
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+// 超过这个时长才评分视为中途离开，用时按上限计
+const MAX_TIME_SPENT: Duration = Duration::from_secs(300);
+
+struct Current {
+    word: Word,
+    shown_at: Instant, // 显示或上次评分的时间，用于计算答题用时
+}
+
+// 当前显示的单词由后端维护，窗口隐藏或重新显示时从这里恢复
+static CURRENT_WORD: Lazy<Mutex<Option<Current>>> = Lazy::new(|| Mutex::new(None));
+
+pub fn get() -> Option<Word> {
+    CURRENT_WORD.lock().unwrap().as_ref().map(|current| current.word.clone())
+}
+
+/// 给单词评分时调用：返回从单词显示到现在的秒数，并重新开始计时
+///
+/// 评分的不是当前显示的单词时无法得知用时，返回 0。
+pub fn take_time_spent(word_id: &str) -> u64 {
+    let mut current = CURRENT_WORD.lock().unwrap();
+    match current.as_mut() {
+        Some(current) if current.word.id == word_id => {
+            let elapsed = current.shown_at.elapsed().min(MAX_TIME_SPENT);
+            current.shown_at = Instant::now();
+            elapsed.as_secs()
+        }
+        _ => 0,
+    }
+}
+
+/// 单词列表被整体替换后（外部修改重新加载）同步当前单词
+///
+/// 当前单词仍存在时更新为新的内容，不重新计时；已被删除时切换到下一个单词。
+pub async fn revalidate(app: &AppHandle) -> Result<()> {
+    let Some(current) = get() else {
+        next(app).await?;
+        return Ok(());
+    };
+
+    match xml_parser::get_word_by_id(&current.id).await? {
+        Some(word) if word == current => {}
+        Some(word) => {
+            if let Some(current) = CURRENT_WORD.lock().unwrap().as_mut() {
+                current.word = word.clone();
+            }
+            tray::refresh(app);
+            if let Err(e) = app.emit("current-word-changed", &word) {
+                log::warn!("发送当前单词事件失败: {}", e);
+            }
+        }
+        None => {
+            next(app).await?;
+        }
+    }
+    Ok(())
+}
+
+/// 切换到下一个推荐单词
+pub async fn next(app: &AppHandle) -> Result<Option<Word>> {
+    let current_id = get().map(|w| w.id);
+    let word = learning::get_next_word(current_id.as_deref()).await?;
+    if let Some(word) = &word {
+        set(app, word.clone());
+    }
+    Ok(word)
+}
+
+/// 切换到上一个推荐单词
+pub async fn previous(app: &AppHandle) -> Result<Option<Word>> {
+    let current_id = get().map(|w| w.id);
+    let word = learning::get_previous_word(current_id.as_deref()).await?;
+    if let Some(word) = &word {
+        set(app, word.clone());
+    }
+    Ok(word)
+}
+
+/// 按 id 切换当前单词（前端搜索、列表选择等），与当前单词相同时不做任何事
+pub async fn select(app: &AppHandle, id: &str) -> Result<Word> {
+    if let Some(current) = get() {
+        if current.id == id {
+            return Ok(current);
+        }
+    }
+
+    let word = xml_parser::get_word_by_id(id)
+        .await?
+        .ok_or_else(|| anyhow!("单词不存在: {}", id))?;
+    set(app, word.clone());
+    Ok(word)
+}
+
+fn set(app: &AppHandle, word: Word) {
+    *CURRENT_WORD.lock().unwrap() = Some(Current {
+        word: word.clone(),
+        shown_at: Instant::now(),
+    });
+
+    // 手动切换后重新开始自动切换的计时
+    auto_switch::reset_countdown();
+    tray::refresh(app);
+
+    if let Err(e) = app.emit("current-word-changed", &word) {
+        log::warn!("发送当前单词事件失败: {}", e);
+    }
+}
@@ -0,0 +1,307 @@
+use crate::models::{Word, WordProgress, DuplicateGroup, DuplicateReason};
+use crate::services::xml_parser;
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+
+// 释义相似度达到该阈值才视为疑似重复
+const TRANSLATION_SIMILARITY_THRESHOLD: f64 = 0.8;
+
+/// 查找疑似重复的单词，按可能性从高到低排序
+///
+/// `progresses` 为学习进度存储中的记录，用于选出进度最好的单词作为合并目标。
+pub async fn find_duplicates(progresses: &HashMap<String, WordProgress>) -> Result<Vec<DuplicateGroup>> {
+    let mut words = xml_parser::load_words(None).await?;
+    for word in &mut words {
+        if let Some(progress) = progresses.get(&word.id) {
+            word.apply_progress(progress);
+        }
+    }
+    Ok(group_duplicates(&words))
+}
+
+/// 将 `duplicate_ids` 合并进 `primary_id`
+///
+/// 例句、标签和备注取并集，学习进度保留各单词中最好的一份，
+/// 返回合并后的单词以及被移除的单词。
+pub async fn merge_words(
+    primary_id: &str,
+    duplicate_ids: &[String],
+    progresses: &HashMap<String, WordProgress>,
+) -> Result<(Word, Vec<Word>)> {
+    if duplicate_ids.iter().any(|id| id == primary_id) {
+        return Err(anyhow!("不能将单词合并到自身"));
+    }
+
+    xml_parser::modify_words(|words| {
+        let primary_index = words
+            .iter()
+            .position(|w| w.id == primary_id)
+            .ok_or_else(|| anyhow!("未找到单词: {}", primary_id))?;
+
+        for id in duplicate_ids {
+            if !words.iter().any(|w| &w.id == id) {
+                return Err(anyhow!("未找到单词: {}", id));
+            }
+        }
+
+        // 单词本文件中没有学习进度，比较进度前先用存储中的记录覆盖
+        for word in words.iter_mut().filter(|w| w.id == primary_id || duplicate_ids.contains(&w.id)) {
+            if let Some(progress) = progresses.get(&word.id) {
+                word.apply_progress(progress);
+            }
+        }
+
+        let duplicates: Vec<Word> = words
+            .iter()
+            .filter(|w| duplicate_ids.contains(&w.id))
+            .cloned()
+            .collect();
+
+        let merged = {
+            let primary = &mut words[primary_index];
+            for duplicate in &duplicates {
+                merge_into(primary, duplicate);
+            }
+            primary.updated_at = Utc::now();
+            primary.clone()
+        };
+
+        words.retain(|w| !duplicate_ids.contains(&w.id));
+
+        log::info!("🔗 已将 {} 个重复单词合并到: {}", duplicates.len(), merged.word);
+        Ok((merged, duplicates))
+    })
+    .await
+}
+
+/// 规范化单词：忽略大小写、首尾空白以及连字符、下划线等分隔符的差异
+pub fn normalize_headword(word: &str) -> String {
+    word.trim()
+        .to_lowercase()
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn normalize_sentence(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// 比较时用到的规范化结果，每个单词只计算一次
+struct Features {
+    headword: String,
+    translation: String,
+    bigrams: HashSet<(char, char)>,
+    example_sources: HashSet<String>,
+}
+
+impl Features {
+    fn new(word: &Word) -> Self {
+        let translation = normalize_sentence(&word.trans);
+        Self {
+            headword: normalize_headword(&word.word),
+            bigrams: bigrams(&translation),
+            translation,
+            example_sources: word
+                .examples
+                .iter()
+                .map(|ex| normalize_sentence(&ex.source))
+                .filter(|source| !source.is_empty())
+                .collect(),
+        }
+    }
+}
+
+fn bigrams(text: &str) -> HashSet<(char, char)> {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() == 1 {
+        return [(chars[0], chars[0])].into_iter().collect();
+    }
+    chars.windows(2).map(|pair| (pair[0], pair[1])).collect()
+}
+
+/// 基于字符二元组的 Jaccard 相似度
+fn translation_similarity(a: &Features, b: &Features) -> f64 {
+    if a.translation.is_empty() || b.translation.is_empty() {
+        return 0.0;
+    }
+    if a.translation == b.translation {
+        return 1.0;
+    }
+
+    let intersection = a.bigrams.intersection(&b.bigrams).count() as f64;
+    let union = a.bigrams.union(&b.bigrams).count() as f64;
+
+    intersection / union
+}
+
+fn compare_pair(a: &Features, b: &Features) -> Option<(Vec<DuplicateReason>, f64)> {
+    let mut reasons = Vec::new();
+    let mut score: f64 = 0.0;
+
+    if a.headword == b.headword {
+        reasons.push(DuplicateReason::SameHeadword);
+        score = score.max(1.0);
+    }
+
+    let similarity = translation_similarity(a, b);
+    if similarity >= TRANSLATION_SIMILARITY_THRESHOLD {
+        reasons.push(DuplicateReason::SimilarTranslation);
+        score = score.max(similarity * 0.8);
+    }
+
+    if !a.example_sources.is_disjoint(&b.example_sources) {
+        reasons.push(DuplicateReason::SharedExamples);
+        score = score.max(0.7);
+    }
+
+    if reasons.is_empty() {
+        return None;
+    }
+
+    // 多个依据同时成立时提高可信度
+    let bonus = 0.1 * (reasons.len() - 1) as f64;
+    Some((reasons, (score + bonus).min(1.0)))
+}
+
+fn group_duplicates(words: &[Word]) -> Vec<DuplicateGroup> {
+    // 并查集，将两两相似的单词聚合为一组
+    let mut parent: Vec<usize> = (0..words.len()).collect();
+
+    fn find(parent: &mut [usize], i: usize) -> usize {
+        let mut root = i;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        let mut node = i;
+        while parent[node] != root {
+            let next = parent[node];
+            parent[node] = root;
+            node = next;
+        }
+        root
+    }
+
+    let features: Vec<Features> = words.iter().map(Features::new).collect();
+
+    let mut pair_results: Vec<(usize, usize, Vec<DuplicateReason>, f64)> = Vec::new();
+    for i in 0..words.len() {
+        for j in (i + 1)..words.len() {
+            if let Some((reasons, score)) = compare_pair(&features[i], &features[j]) {
+                let (root_i, root_j) = (find(&mut parent, i), find(&mut parent, j));
+                if root_i != root_j {
+                    parent[root_j] = root_i;
+                }
+                pair_results.push((i, j, reasons, score));
+            }
+        }
+    }
+
+    let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..words.len() {
+        let root = find(&mut parent, i);
+        members.entry(root).or_default().push(i);
+    }
+
+    let mut groups: Vec<DuplicateGroup> = members
+        .into_iter()
+        .filter(|(_, indices)| indices.len() > 1)
+        .map(|(root, indices)| {
+            let mut reasons = Vec::new();
+            let mut score: f64 = 0.0;
+            for (i, _, pair_reasons, pair_score) in &pair_results {
+                if find(&mut parent, *i) != root {
+                    continue;
+                }
+                for reason in pair_reasons {
+                    if !reasons.contains(reason) {
+                        reasons.push(*reason);
+                    }
+                }
+                score = score.max(*pair_score);
+            }
+
+            let group_words: Vec<Word> = indices.iter().map(|&i| words[i].clone()).collect();
+            let primary_id = pick_primary(&group_words).id.clone();
+
+            DuplicateGroup {
+                primary_id,
+                words: group_words,
+                reasons,
+                score,
+            }
+        })
+        .collect();
+
+    groups.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    groups
+}
+
+/// 选择学习进度最好的单词作为合并目标，进度相同时保留最早创建的
+fn pick_primary(words: &[Word]) -> &Word {
+    words
+        .iter()
+        .max_by(|a, b| {
+            word_rank(a)
+                .cmp(&word_rank(b))
+                .then_with(|| b.created_at.cmp(&a.created_at))
+        })
+        .expect("重复分组至少包含两个单词")
+}
+
+fn word_rank(word: &Word) -> (u8, u32, u8) {
+    progress_rank(word.mastery_level, word.review_count, word.progress)
+}
+
+/// 比较学习进度的排序键：掌握度优先，其次是复习次数和进度
+///
+/// 选择合并目标和合并时保留哪份学习进度都按这个顺序比较。
+pub fn progress_rank(mastery_level: u8, review_count: u32, progress: u8) -> (u8, u32, u8) {
+    (mastery_level, review_count, progress)
+}
+
+fn merge_into(primary: &mut Word, duplicate: &Word) {
+    // 例句按规范化后的原文去重
+    for example in &duplicate.examples {
+        let key = normalize_sentence(&example.source);
+        if !primary.examples.iter().any(|ex| normalize_sentence(&ex.source) == key) {
+            primary.examples.push(example.clone());
+        }
+    }
+
+    for tag in &duplicate.tags {
+        if !primary.tags.contains(tag) {
+            primary.tags.push(tag.clone());
+        }
+    }
+
+    let note = duplicate.note.trim();
+    if !note.is_empty() && !primary.note.contains(note) {
+        primary.note = if primary.note.trim().is_empty() {
+            note.to_string()
+        } else {
+            format!("{}；{}", primary.note.trim(), note)
+        };
+    }
+
+    if primary.phonetic.trim().is_empty() {
+        primary.phonetic = duplicate.phonetic.clone();
+    }
+
+    // 保留更好的学习进度
+    if word_rank(duplicate) > word_rank(primary) {
+        primary.progress = duplicate.progress;
+        primary.mastery_level = duplicate.mastery_level;
+        primary.review_count = duplicate.review_count;
+        primary.last_review = duplicate.last_review;
+    }
+
+    if duplicate.created_at < primary.created_at {
+        primary.created_at = duplicate.created_at;
+    }
+}
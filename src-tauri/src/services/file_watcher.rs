@@ -0,0 +1,287 @@
+use crate::models::{Settings, FieldError};
+use crate::services::{settings, wordbook, xml_parser};
+use crate::utils::{atomic_file, config, settings_migration};
+use anyhow::{Result, anyhow};
+use notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+// 编辑器保存文件时往往触发多次事件，合并一段时间内的变化
+const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchedFile {
+    Settings,
+    Wordbook,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalChangeEvent {
+    pub file: WatchedFile,
+    pub errors: Vec<FieldError>, // 仅在修改被拒绝时非空
+}
+
+struct WatchedPaths {
+    settings: PathBuf,
+    wordbook: PathBuf,
+}
+
+// 保持 watcher 存活
+static WATCHER: Lazy<Mutex<Option<Debouncer<RecommendedWatcher>>>> = Lazy::new(|| Mutex::new(None));
+// 最近一次应用的设置，用于和外部修改后的内容比较
+static LAST_SETTINGS: Lazy<Mutex<Option<Settings>>> = Lazy::new(|| Mutex::new(None));
+// 前端存在未保存的修改时，外部修改需要用户确认
+static UNSAVED_CHANGES: AtomicBool = AtomicBool::new(false);
+static PENDING_CONFLICTS: Lazy<Mutex<HashSet<WatchedFile>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// 开始监听设置文件和词汇文件所在目录
+pub async fn start(app: &AppHandle) -> Result<()> {
+    let paths = WatchedPaths {
+        settings: config::get_config_file_path()?,
+        wordbook: xml_parser::get_vocabulary_file_path()?,
+    };
+
+    *LAST_SETTINGS.lock().unwrap() = Some(config::load_settings().await?);
+    settings::subscribe(|_, change| {
+        *LAST_SETTINGS.lock().unwrap() = Some(change.new.clone());
+    });
+
+    let handler_app = app.clone();
+    let settings_path = paths.settings.clone();
+
+    let mut debouncer = new_debouncer(DEBOUNCE_INTERVAL, move |result: DebounceEventResult| {
+        let events = match result {
+            Ok(events) => events,
+            Err(e) => {
+                log::warn!("文件监听出错: {}", e);
+                return;
+            }
+        };
+
+        let mut changed = HashSet::new();
+        for event in events {
+            if event.path == settings_path {
+                changed.insert(WatchedFile::Settings);
+            } else if xml_parser::get_vocabulary_file_path().is_ok_and(|path| event.path == path) {
+                // 单词本可以切换，按当前使用的文件比较
+                changed.insert(WatchedFile::Wordbook);
+            }
+        }
+
+        for file in changed {
+            let app = handler_app.clone();
+            tauri::async_runtime::spawn(async move {
+                handle_external_change(&app, file).await;
+            });
+        }
+    })?;
+
+    let mut directories: Vec<PathBuf> = [&paths.settings, &paths.wordbook]
+        .iter()
+        .filter_map(|path| path.parent().map(|p| p.to_path_buf()))
+        .collect();
+    directories.dedup();
+
+    for directory in directories {
+        fs::create_dir_all(&directory)?;
+        debouncer
+            .watcher()
+            .watch(&directory, RecursiveMode::NonRecursive)
+            .map_err(|e| anyhow!("无法监听目录 {:?}: {}", directory, e))?;
+        log::info!("👀 开始监听目录: {:?}", directory);
+    }
+
+    *WATCHER.lock().unwrap() = Some(debouncer);
+    Ok(())
+}
+
+/// 前端告知是否存在未保存的修改
+pub fn set_unsaved_changes(unsaved: bool) {
+    UNSAVED_CHANGES.store(unsaved, Ordering::SeqCst);
+}
+
+/// 处理冲突：`accept` 为 true 时加载外部修改，否则用程序中的数据覆盖文件
+pub async fn resolve_conflict(app: &AppHandle, file: WatchedFile, accept: bool) -> Result<()> {
+    if !PENDING_CONFLICTS.lock().unwrap().remove(&file) {
+        return Err(anyhow!("没有待处理的外部修改"));
+    }
+
+    if accept {
+        let content = read_watched_file(file)?;
+        apply_external_change(app, file, &content).await;
+        return Ok(());
+    }
+
+    match file {
+        WatchedFile::Settings => {
+            let current = LAST_SETTINGS.lock().unwrap().clone();
+            if let Some(current) = current {
+                config::save_settings(&current).await?;
+            }
+        }
+        WatchedFile::Wordbook => xml_parser::save_cache().await?,
+    }
+
+    log::info!("📝 已保留程序中的数据，覆盖外部修改: {:?}", file);
+    Ok(())
+}
+
+fn watched_path(file: WatchedFile) -> Result<PathBuf> {
+    match file {
+        WatchedFile::Settings => config::get_config_file_path(),
+        WatchedFile::Wordbook => xml_parser::get_vocabulary_file_path(),
+    }
+}
+
+fn read_watched_file(file: WatchedFile) -> Result<String> {
+    let path = watched_path(file)?;
+    fs::read_to_string(&path).map_err(|e| anyhow!("无法读取文件 {:?}: {}", path, e))
+}
+
+async fn handle_external_change(app: &AppHandle, file: WatchedFile) {
+    let path = match watched_path(file) {
+        Ok(path) => path,
+        Err(_) => return,
+    };
+
+    // 文件被删除或正在被替换时忽略
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(_) => return,
+    };
+
+    // 忽略本程序自己的写入
+    if atomic_file::is_own_write(&path, content.as_bytes()) {
+        return;
+    }
+
+    log::info!("📝 检测到外部修改: {:?}", path);
+
+    if UNSAVED_CHANGES.load(Ordering::SeqCst) {
+        PENDING_CONFLICTS.lock().unwrap().insert(file);
+        emit(app, "external-change-conflict", file, Vec::new());
+        return;
+    }
+
+    apply_external_change(app, file, &content).await;
+}
+
+async fn apply_external_change(app: &AppHandle, file: WatchedFile, content: &str) {
+    let result = match file {
+        WatchedFile::Settings => apply_settings(app, content).await,
+        WatchedFile::Wordbook => apply_wordbook(app, content).await,
+    };
+
+    match result {
+        Ok(()) => emit(app, "external-change-applied", file, Vec::new()),
+        Err(errors) => {
+            log::warn!("⚠️ 外部修改未通过校验，已忽略: {:?} {:?}", file, errors);
+            emit(app, "external-change-rejected", file, errors);
+        }
+    }
+}
+
+async fn apply_settings(app: &AppHandle, content: &str) -> Result<(), Vec<FieldError>> {
+    let loaded = match parse_valid_settings(content) {
+        Ok(loaded) => loaded,
+        Err(errors) => {
+            restore_settings().await;
+            return Err(errors);
+        }
+    };
+
+    if loaded.migrated {
+        config::save_settings(&loaded.settings)
+            .await
+            .map_err(|e| vec![FieldError::new("", e.to_string())])?;
+    }
+
+    let old = LAST_SETTINGS.lock().unwrap().clone().unwrap_or_default();
+    settings::broadcast(app, old, loaded.settings);
+    Ok(())
+}
+
+async fn apply_wordbook(app: &AppHandle, content: &str) -> Result<(), Vec<FieldError>> {
+    match xml_parser::reload_from_content(content).await {
+        Ok(count) => {
+            log::info!("✅ 已重新加载词汇文件，共 {} 个单词", count);
+            wordbook::notify_reloaded(app).await;
+            Ok(())
+        }
+        Err(e) => {
+            restore_wordbook().await;
+            Err(vec![FieldError::new("", e.to_string())])
+        }
+    }
+}
+
+fn parse_valid_settings(content: &str) -> Result<settings_migration::LoadedSettings, Vec<FieldError>> {
+    let loaded = settings_migration::parse_settings(content)
+        .map_err(|e| vec![FieldError::new("", e.to_string())])?;
+
+    let errors = settings::validate(&loaded.settings);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(loaded)
+}
+
+// 被拒绝的外部修改另存一份，再写回最近一次有效的设置，
+// 否则下次读取设置（包括重启后）仍会拿到无效的内容
+async fn restore_settings() {
+    let Some(last) = LAST_SETTINGS.lock().unwrap().clone() else {
+        return;
+    };
+
+    match config::get_config_file_path() {
+        Ok(path) if path.exists() => {
+            if let Err(e) = atomic_file::preserve_aside(&path, "rejected") {
+                log::warn!("保留被拒绝的设置文件失败: {}", e);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("获取设置文件路径失败: {}", e),
+    }
+
+    match config::save_settings(&last).await {
+        Ok(()) => log::info!("↩️ 已恢复为最近一次有效的设置"),
+        Err(e) => log::error!("❌ 恢复设置失败: {}", e),
+    }
+}
+
+// 与设置相同：无法解析的单词本另存一份，再写回缓存中的单词，否则下次启动时加载失败
+async fn restore_wordbook() {
+    match xml_parser::get_vocabulary_file_path() {
+        Ok(path) if path.exists() => {
+            if let Err(e) = atomic_file::preserve_aside(&path, "rejected") {
+                log::warn!("保留被拒绝的词汇文件失败: {}", e);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("获取词汇文件路径失败: {}", e),
+    }
+
+    // 没有缓存时不写入，下次加载时从 .bak 恢复
+    if !xml_parser::is_cached() {
+        return;
+    }
+    match xml_parser::save_cache().await {
+        Ok(()) => log::info!("↩️ 已恢复为程序中的单词数据"),
+        Err(e) => log::error!("❌ 恢复词汇文件失败: {}", e),
+    }
+}
+
+fn emit(app: &AppHandle, event: &str, file: WatchedFile, errors: Vec<FieldError>) {
+    if let Err(e) = app.emit(event, ExternalChangeEvent { file, errors }) {
+        log::warn!("发送文件变更事件失败: {}", e);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::models::{AppAction, HotkeyConfig, HotkeyScope};
+use crate::services::actions;
+use crate::services::settings::{self, SettingsChange, SettingsSection};
+use crate::utils::hotkey::{self, HotkeyIssue, HotkeyIssueKind};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+/// 单个操作的快捷键注册结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeyStatus {
+    pub action: AppAction,
+    pub shortcut: String,
+    pub registered: bool,
+    pub error: Option<String>, // 注册失败的原因
+    pub suggestions: Vec<String>, // 注册失败时可用的替代组合
+}
+
+// 当前由本模块注册的快捷键，重新绑定时先注销
+static REGISTERED: Lazy<Mutex<Vec<Shortcut>>> = Lazy::new(|| Mutex::new(Vec::new()));
+static STATUS: Lazy<Mutex<Vec<HotkeyStatus>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// 订阅设置变更，快捷键配置修改后重新注册
+pub fn register_settings_subscriber() {
+    settings::subscribe(on_settings_changed);
+}
+
+fn on_settings_changed(app: &AppHandle, change: &SettingsChange) {
+    if change.touches(SettingsSection::Hotkeys) {
+        register_all(app, &change.new.hotkeys);
+    }
+}
+
+/// 按配置注册全部全局快捷键，返回每个全局绑定的注册结果
+///
+/// 先注销上一次注册的快捷键；无效、不安全或重复的快捷键不会注册，
+/// 单个快捷键注册失败不影响其他操作。窗口内快捷键由前端处理。
+pub fn register_all(app: &AppHandle, config: &HotkeyConfig) -> Vec<HotkeyStatus> {
+    unregister_all(app);
+
+    let issues = hotkey::check_config(config);
+    let taken = hotkey::parsed_shortcuts(config);
+
+    let mut statuses = Vec::new();
+    for (action, shortcut, scope) in config.entries() {
+        if scope != HotkeyScope::Global {
+            continue;
+        }
+
+        let status = match issues.iter().find(|issue| issue.action == action) {
+            Some(issue) => HotkeyStatus {
+                action,
+                shortcut: shortcut.to_string(),
+                registered: false,
+                error: Some(issue.message.clone()),
+                suggestions: issue.suggestions.clone(),
+            },
+            None => register_action(app, action, shortcut, &taken),
+        };
+        statuses.push(status);
+    }
+
+    let failed: Vec<HotkeyStatus> = statuses
+        .iter()
+        .filter(|status| status.error.is_some())
+        .cloned()
+        .collect();
+    if !failed.is_empty() {
+        log::warn!("⚠️ 部分全局快捷键注册失败: {:?}", failed);
+        if let Err(e) = app.emit("hotkey-registration-failed", &failed) {
+            log::warn!("发送快捷键注册失败事件失败: {}", e);
+        }
+    }
+
+    *STATUS.lock().unwrap() = statuses.clone();
+    statuses
+}
+
+/// 最近一次注册的结果
+pub fn get_status() -> Vec<HotkeyStatus> {
+    STATUS.lock().unwrap().clone()
+}
+
+/// 检查快捷键配置，除配置本身的问题外，还会试注册尚未由本程序占用的快捷键，
+/// 找出已被系统或其他程序占用的组合，替代建议同样只保留可以注册的组合
+pub fn check(app: &AppHandle, config: &HotkeyConfig) -> Vec<HotkeyIssue> {
+    let mut issues = hotkey::check_config(config);
+    let taken = hotkey::parsed_shortcuts(config);
+
+    for (action, shortcut, scope) in config.entries() {
+        if scope != HotkeyScope::Global || issues.iter().any(|issue| issue.action == action) {
+            continue;
+        }
+        let Ok(parsed) = hotkey::parse(shortcut) else {
+            continue;
+        };
+        if !is_available(app, parsed) {
+            issues.push(conflict_issue(app, action, shortcut, &taken));
+        }
+    }
+
+    for issue in &mut issues {
+        issue.suggestions.retain(|candidate| {
+            hotkey::parse(candidate)
+                .map(|parsed| is_available(app, parsed))
+                .unwrap_or(false)
+        });
+    }
+
+    issues
+}
+
+fn register_action(app: &AppHandle, action: AppAction, shortcut: &str, taken: &[Shortcut]) -> HotkeyStatus {
+    let mut status = HotkeyStatus {
+        action,
+        shortcut: shortcut.to_string(),
+        registered: false,
+        error: None,
+        suggestions: Vec::new(),
+    };
+
+    let parsed = match hotkey::parse(shortcut) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            status.error = Some(e.to_string());
+            return status;
+        }
+    };
+
+    // 全局快捷键会拦截所有程序中的按键，没有 Ctrl/Alt/Cmd 修饰键（如单独的 Space）时拒绝注册
+    if hotkey::is_unsafe(&parsed) {
+        status.error = Some(format!("快捷键 {} 缺少 Ctrl/Alt/Cmd 修饰键，会影响其他程序的输入", shortcut));
+        return status;
+    }
+
+    let result = app.global_shortcut().on_shortcut(parsed, move |app, _shortcut, event| {
+        // 只响应按下，忽略松开事件
+        if event.state == ShortcutState::Pressed {
+            actions::trigger(app, action);
+        }
+    });
+
+    match result {
+        Ok(()) => {
+            REGISTERED.lock().unwrap().push(parsed);
+            status.registered = true;
+            log::info!("⌨️ 已注册全局快捷键: {:?} -> {}", action, shortcut);
+        }
+        Err(e) => {
+            // 多数情况下是被系统或其他程序占用
+            let issue = conflict_issue(app, action, shortcut, taken);
+            status.error = Some(format!("{}: {}", issue.message, e));
+            status.suggestions = issue.suggestions;
+        }
+    }
+
+    status
+}
+
+fn conflict_issue(app: &AppHandle, action: AppAction, shortcut: &str, taken: &[Shortcut]) -> HotkeyIssue {
+    let mut suggestions = hotkey::suggest_alternatives(shortcut, taken);
+    suggestions.retain(|candidate| {
+        hotkey::parse(candidate)
+            .map(|parsed| is_available(app, parsed))
+            .unwrap_or(false)
+    });
+
+    HotkeyIssue {
+        action,
+        shortcut: shortcut.to_string(),
+        kind: HotkeyIssueKind::Conflict,
+        message: format!("快捷键 {} 已被系统或其他程序占用", shortcut),
+        suggestions,
+    }
+}
+
+/// 快捷键是否可以注册：已由本程序注册的视为可用，否则试注册后立即注销
+fn is_available(app: &AppHandle, shortcut: Shortcut) -> bool {
+    if REGISTERED.lock().unwrap().contains(&shortcut) {
+        return true;
+    }
+
+    let global_shortcut = app.global_shortcut();
+    if global_shortcut.is_registered(shortcut) {
+        return false;
+    }
+
+    match global_shortcut.register(shortcut) {
+        Ok(()) => {
+            let _ = global_shortcut.unregister(shortcut);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+fn unregister_all(app: &AppHandle) {
+    let registered: Vec<Shortcut> = std::mem::take(&mut *REGISTERED.lock().unwrap());
+    for shortcut in registered {
+        if let Err(e) = app.global_shortcut().unregister(shortcut) {
+            log::warn!("注销全局快捷键失败 {}: {}", shortcut.into_string(), e);
+        }
+    }
+}
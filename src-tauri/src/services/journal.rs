@@ -0,0 +1,349 @@
+use crate::models::{ReviewRecord, Word, Settings};
+use crate::services::{xml_parser, settings, review_log};
+use crate::utils::{config, atomic_file, stores};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::sync::Mutex;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+// 撤销历史的最大条数
+const MAX_HISTORY: usize = 100;
+// 其中评分记录的最大条数，评分频繁，单独限制以免挤掉对单词和设置的修改
+const MAX_REVIEW_HISTORY: usize = 20;
+// 磁盘上保留的快照文件数量
+const MAX_SNAPSHOTS: usize = 10;
+// 参与快照的学习数据存储；复习记录只追加，重置时整体归档（见 review_log::archive），不放进快照
+pub const PROGRESS_STORES: [&str; 4] = [
+    "user_progress.json",
+    "learning_stats.json",
+    "study_sessions.json",
+    "achievements.json",
+];
+
+/// 一次可逆的修改，`before` 为 None 表示修改前不存在，`after` 为 None 表示修改后被删除
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Change {
+    Word {
+        id: String,
+        before: Option<Word>,
+        after: Option<Word>,
+    },
+    StoreEntry {
+        store: String,
+        key: String,
+        before: Option<serde_json::Value>,
+        after: Option<serde_json::Value>,
+    },
+    Settings {
+        before: Box<Settings>,
+        after: Box<Settings>,
+    },
+    // 清空复习记录：修改前记录在日志中，修改后移到名为 `archive` 的归档
+    ReviewLogArchive {
+        archive: String,
+    },
+    // 评分追加的复习记录：修改前不存在，修改后在日志中
+    ReviewRecord {
+        record: ReviewRecord,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub id: String,
+    pub label: String,
+    pub timestamp: DateTime<Utc>,
+    pub changes: Vec<Change>,
+    #[serde(default)]
+    pub review: bool, // 评分产生的记录，受 MAX_REVIEW_HISTORY 限制
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalSummary {
+    pub id: String,
+    pub label: String,
+    pub timestamp: DateTime<Utc>,
+    pub change_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalState {
+    pub undo: Vec<JournalSummary>, // 最近的操作在前
+    pub redo: Vec<JournalSummary>,
+}
+
+#[derive(Default)]
+struct Journal {
+    undo: VecDeque<JournalEntry>,
+    redo: Vec<JournalEntry>,
+}
+
+static JOURNAL: Lazy<Mutex<Journal>> = Lazy::new(|| Mutex::new(Journal::default()));
+// 防止撤销与重做同时执行
+static APPLY_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+impl From<&JournalEntry> for JournalSummary {
+    fn from(entry: &JournalEntry) -> Self {
+        Self {
+            id: entry.id.clone(),
+            label: entry.label.clone(),
+            timestamp: entry.timestamp,
+            change_count: entry.changes.len() as u32,
+        }
+    }
+}
+
+/// 记录一次操作，新的操作会清空重做历史
+pub fn record(label: &str, changes: Vec<Change>) {
+    push(label, changes, false);
+}
+
+/// 记录一次评分（复习、标记认识或不认识）
+///
+/// 评分只保留最近 [`MAX_REVIEW_HISTORY`] 条，超出时丢弃最早的评分，其它操作不受影响。
+pub fn record_review(label: &str, changes: Vec<Change>) {
+    push(label, changes, true);
+}
+
+fn push(label: &str, changes: Vec<Change>, review: bool) {
+    if changes.is_empty() {
+        return;
+    }
+
+    let entry = JournalEntry {
+        id: Uuid::new_v4().to_string(),
+        label: label.to_string(),
+        timestamp: Utc::now(),
+        changes,
+        review,
+    };
+
+    let mut journal = JOURNAL.lock().unwrap();
+    journal.undo.push_back(entry);
+    journal.redo.clear();
+    if review && journal.undo.iter().filter(|entry| entry.review).count() > MAX_REVIEW_HISTORY {
+        if let Some(oldest) = journal.undo.iter().position(|entry| entry.review) {
+            journal.undo.remove(oldest);
+        }
+    }
+    while journal.undo.len() > MAX_HISTORY {
+        journal.undo.pop_front();
+    }
+
+    log::debug!("📝 已记录操作: {}", label);
+}
+
+pub fn get_state() -> JournalState {
+    let journal = JOURNAL.lock().unwrap();
+    JournalState {
+        undo: journal.undo.iter().rev().map(JournalSummary::from).collect(),
+        redo: journal.redo.iter().rev().map(JournalSummary::from).collect(),
+    }
+}
+
+/// 撤销最近一次操作，没有可撤销的操作时返回 None
+pub async fn undo(app: &AppHandle) -> Result<Option<JournalSummary>> {
+    let _guard = APPLY_LOCK.lock().await;
+
+    let entry = match JOURNAL.lock().unwrap().undo.pop_back() {
+        Some(entry) => entry,
+        None => return Ok(None),
+    };
+
+    if let Err(e) = apply_changes(app, &entry, true).await {
+        JOURNAL.lock().unwrap().undo.push_back(entry);
+        return Err(anyhow!("撤销失败: {}", e));
+    }
+
+    let summary = JournalSummary::from(&entry);
+    JOURNAL.lock().unwrap().redo.push(entry);
+
+    log::info!("↩️ 已撤销: {}", summary.label);
+    Ok(Some(summary))
+}
+
+/// 重做最近一次撤销的操作，没有可重做的操作时返回 None
+pub async fn redo(app: &AppHandle) -> Result<Option<JournalSummary>> {
+    let _guard = APPLY_LOCK.lock().await;
+
+    let entry = match JOURNAL.lock().unwrap().redo.pop() {
+        Some(entry) => entry,
+        None => return Ok(None),
+    };
+
+    if let Err(e) = apply_changes(app, &entry, false).await {
+        JOURNAL.lock().unwrap().redo.push(entry);
+        return Err(anyhow!("重做失败: {}", e));
+    }
+
+    let summary = JournalSummary::from(&entry);
+    JOURNAL.lock().unwrap().undo.push_back(entry);
+
+    log::info!("↪️ 已重做: {}", summary.label);
+    Ok(Some(summary))
+}
+
+/// 撤销时倒序恢复 `before`，重做时顺序应用 `after`
+///
+/// 中途失败时把已经应用的修改恢复原状，操作要么完整生效，要么不生效。
+async fn apply_changes(app: &AppHandle, entry: &JournalEntry, use_before: bool) -> Result<()> {
+    let ordered: Vec<&Change> = if use_before {
+        entry.changes.iter().rev().collect()
+    } else {
+        entry.changes.iter().collect()
+    };
+
+    for (index, change) in ordered.iter().enumerate() {
+        if let Err(e) = apply_change(app, change, use_before).await {
+            for applied in ordered[..index].iter().rev() {
+                if let Err(rollback) = apply_change(app, applied, !use_before).await {
+                    log::error!("回滚操作 {} 失败: {}", entry.label, rollback);
+                }
+            }
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
+
+async fn apply_change(app: &AppHandle, change: &Change, use_before: bool) -> Result<()> {
+    match change {
+        Change::Word { id, before, after } => {
+            let target = if use_before { before } else { after };
+            xml_parser::modify_words(|words| {
+                let position = words.iter().position(|w| &w.id == id);
+                match (position, target) {
+                    (Some(index), Some(word)) => words[index] = word.clone(),
+                    (None, Some(word)) => words.push(word.clone()),
+                    (Some(index), None) => {
+                        words.remove(index);
+                    }
+                    (None, None) => {}
+                }
+                Ok(())
+            })
+            .await
+        }
+        Change::StoreEntry { store, key, before, after } => {
+            let target = if use_before { before } else { after };
+            let name = store.as_str();
+            let store = stores::open(app, name)?;
+            match target {
+                Some(value) => store.set(key.as_str(), value.clone()),
+                None => {
+                    store.delete(key.as_str());
+                }
+            }
+            stores::save(app, name)?;
+            Ok(())
+        }
+        Change::Settings { before, after } => {
+            let target = if use_before { before } else { after };
+            settings::persist(app, target.as_ref().clone()).await?;
+            Ok(())
+        }
+        Change::ReviewLogArchive { archive } => {
+            if use_before {
+                review_log::restore(archive)
+            } else {
+                review_log::archive(app, archive)
+            }
+        }
+        Change::ReviewRecord { record } => {
+            if use_before {
+                review_log::remove(app, record)
+            } else {
+                review_log::append(app, record)
+            }
+        }
+    }
+}
+
+/// 读取指定单词的当前状态，用于在修改前后生成 [`Change::Word`]
+pub async fn capture_words(ids: &[String]) -> Result<HashMap<String, Word>> {
+    let words = xml_parser::load_words(None).await?;
+    Ok(words
+        .into_iter()
+        .filter(|w| ids.contains(&w.id))
+        .map(|w| (w.id.clone(), w))
+        .collect())
+}
+
+pub fn word_changes(ids: &[String], before: &HashMap<String, Word>, after: &HashMap<String, Word>) -> Vec<Change> {
+    ids.iter()
+        .filter(|id| before.get(*id) != after.get(*id))
+        .map(|id| Change::Word {
+            id: id.clone(),
+            before: before.get(id).cloned(),
+            after: after.get(id).cloned(),
+        })
+        .collect()
+}
+
+/// 在破坏性操作前把学习数据和单词库完整写入磁盘快照，返回快照文件路径
+pub async fn take_snapshot(app: &AppHandle, reason: &str) -> Result<String> {
+    let mut stores = serde_json::Map::new();
+    for name in PROGRESS_STORES {
+        let store = stores::open(app, name)?;
+        let entries: serde_json::Map<String, serde_json::Value> = store.entries().into_iter().collect();
+        stores.insert(name.to_string(), serde_json::Value::Object(entries));
+    }
+
+    let words = xml_parser::load_words(None).await?;
+
+    let snapshot = serde_json::json!({
+        "snapshot_time": Utc::now().to_rfc3339(),
+        "reason": reason,
+        "app_version": env!("CARGO_PKG_VERSION"),
+        "stores": stores,
+        "words": words,
+    });
+
+    let snapshot_dir = get_snapshot_directory()?;
+    fs::create_dir_all(&snapshot_dir)
+        .map_err(|e| anyhow!("无法创建快照目录: {}", e))?;
+
+    let snapshot_path = snapshot_dir.join(format!("snapshot_{}.json", Utc::now().format("%Y%m%d_%H%M%S_%3f")));
+    atomic_file::write_atomic(&snapshot_path, serde_json::to_string_pretty(&snapshot)?)
+        .map_err(|e| anyhow!("无法写入快照文件: {}", e))?;
+
+    prune_snapshots(&snapshot_dir);
+
+    log::info!("📸 已创建快照 ({}): {:?}", reason, snapshot_path);
+    Ok(snapshot_path.to_string_lossy().to_string())
+}
+
+fn get_snapshot_directory() -> Result<std::path::PathBuf> {
+    config::get_data_directory().map(|dir| dir.join("user").join("snapshots"))
+}
+
+fn prune_snapshots(snapshot_dir: &std::path::Path) {
+    let mut snapshots: Vec<_> = match fs::read_dir(snapshot_dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .map(|name| name.to_string_lossy().starts_with("snapshot_"))
+                    .unwrap_or(false)
+            })
+            .collect(),
+        Err(_) => return,
+    };
+
+    // 文件名包含时间戳，按名称排序即按时间排序
+    snapshots.sort();
+    while snapshots.len() > MAX_SNAPSHOTS {
+        let oldest = snapshots.remove(0);
+        if let Err(e) = fs::remove_file(&oldest) {
+            log::warn!("删除旧快照失败: {:?} - {}", oldest, e);
+        }
+    }
+}
@@ -1,6 +1,6 @@
-use crate::models::{Word, LearningStats};
-use crate::services::xml_parser;
-use crate::utils::config;
+use crate::models::{Word, LearningStats, ReviewMode, DifficultyPreference};
+use crate::services::{journal, xml_parser};
+use crate::utils::{config, atomic_file};
 use anyhow::Result;
 use chrono::{Utc, Local, Datelike};
 
@@ -53,25 +53,24 @@ pub async fn get_previous_word(current_id: Option<&str>) -> Result<Option<Word>>
 pub async fn get_recommended_words(words: &[Word]) -> Result<Vec<Word>> {
     let settings = config::load_settings().await?;
     
-    let mut recommended = match settings.learning.review_mode.as_str() {
-        "smart" => get_smart_recommended_words(words).await?,
-        "sequential" => words.to_vec(),
-        "random" => {
+    let mut recommended = match settings.learning.review_mode {
+        ReviewMode::Smart => get_smart_recommended_words(words).await?,
+        ReviewMode::Sequential => words.to_vec(),
+        ReviewMode::Random => {
             let mut shuffled = words.to_vec();
             use rand::seq::SliceRandom;
             let mut rng = rand::thread_rng();
             shuffled.shuffle(&mut rng);
             shuffled
         }
-        _ => get_smart_recommended_words(words).await?,
     };
     
     // 根据难度偏好过滤
-    match settings.learning.difficulty_preference.as_str() {
-        "easy" => recommended.retain(|w| w.difficulty <= 3),
-        "medium" => recommended.retain(|w| w.difficulty >= 3 && w.difficulty <= 7),
-        "hard" => recommended.retain(|w| w.difficulty >= 7),
-        _ => {} // "mixed" - 保留所有难度
+    match settings.learning.difficulty_preference {
+        DifficultyPreference::Easy => recommended.retain(|w| w.difficulty <= 3),
+        DifficultyPreference::Medium => recommended.retain(|w| w.difficulty >= 3 && w.difficulty <= 7),
+        DifficultyPreference::Hard => recommended.retain(|w| w.difficulty >= 7),
+        DifficultyPreference::Mixed => {} // 保留所有难度
     }
     
     Ok(recommended)
@@ -126,11 +125,26 @@ fn calculate_word_priority_score(word: &Word) -> f64 {
 }
 
 pub async fn mark_word_known(id: &str) -> Result<()> {
-    xml_parser::update_word_progress(id, 5, true).await
+    mark_word(id, 5, true, "标记认识").await
 }
 
 pub async fn mark_word_unknown(id: &str) -> Result<()> {
-    xml_parser::update_word_progress(id, 1, false).await
+    mark_word(id, 1, false, "标记不认识").await
+}
+
+// 标记结果记入撤销历史，误点后可以恢复单词原来的进度
+async fn mark_word(id: &str, progress: u8, is_correct: bool, action: &str) -> Result<()> {
+    let ids = vec![id.to_string()];
+    let before = journal::capture_words(&ids).await?;
+    xml_parser::update_word_progress(id, progress, is_correct).await?;
+    let after = journal::capture_words(&ids).await?;
+
+    let label = match before.get(id) {
+        Some(word) => format!("{} {}", action, word.word),
+        None => action.to_string(),
+    };
+    journal::record_review(&label, journal::word_changes(&ids, &before, &after));
+    Ok(())
 }
 
 pub async fn get_daily_words(_date: Option<&str>) -> Result<Vec<Word>> {
@@ -263,7 +277,7 @@ pub async fn export_progress(file_path: &str) -> Result<()> {
     });
     
     let json_content = serde_json::to_string_pretty(&export_data)?;
-    std::fs::write(file_path, json_content)?;
+    atomic_file::write_atomic(std::path::Path::new(file_path), json_content)?;
     
     Ok(())
 }
@@ -0,0 +1,232 @@
+use crate::models::{AppAction, Settings};
+use crate::services::settings::{self, SettingsChange, SettingsSection};
+use crate::services::window::MAIN_WINDOW;
+use crate::services::{actions, wordbook};
+use crate::utils::config;
+use anyhow::Result;
+use tauri::menu::{CheckMenuItem, Menu, MenuBuilder, MenuItem, Submenu, SubmenuBuilder};
+use tauri::{AppHandle, Emitter, Manager, Wry};
+use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+
+const APP_NAME: &str = "小驴单词薄";
+// 单词本菜单项的 id 前缀，后接单词本文件名
+const WORDBOOK_PREFIX: &str = "wordbook:";
+
+/// 创建应用菜单并注册菜单事件，之后随设置变化重建
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    rebuild(app, &Settings::default())?;
+
+    app.on_menu_event(|app, event| on_menu_event(app, event.id().as_ref()));
+    settings::subscribe(on_settings_changed);
+
+    // 读取实际设置后更新勾选状态
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match config::load_settings().await {
+            Ok(current) => {
+                if let Err(e) = rebuild(&app, &current) {
+                    log::warn!("重建应用菜单失败: {}", e);
+                }
+            }
+            Err(e) => log::warn!("加载菜单设置失败: {}", e),
+        }
+    });
+
+    Ok(())
+}
+
+/// 按当前设置重建应用菜单（自动切换、窗口置顶和当前单词本的勾选状态）
+pub fn rebuild(app: &AppHandle, settings: &Settings) -> tauri::Result<()> {
+    let menu = build_menu(app, settings)?;
+    app.set_menu(menu)?;
+    Ok(())
+}
+
+fn on_settings_changed(app: &AppHandle, change: &SettingsChange) {
+    let relevant = [SettingsSection::AutoSwitch, SettingsSection::AlwaysOnTop, SettingsSection::ActiveWordbook];
+    if !relevant.iter().any(|section| change.touches(*section)) {
+        return;
+    }
+
+    if let Err(e) = rebuild(app, &change.new) {
+        log::warn!("重建应用菜单失败: {}", e);
+    }
+}
+
+fn build_menu(app: &AppHandle, settings: &Settings) -> tauri::Result<Menu<Wry>> {
+    // 应用菜单 - 小驴单词薄
+    let about_app = MenuItem::with_id(app, "about", "关于小驴单词薄", true, None::<&str>)?;
+    let preferences = MenuItem::with_id(app, "preferences", "偏好设置...", true, Some("CmdOrCtrl+Comma"))?;
+    let quit_app = MenuItem::with_id(app, "quit_app", "退出小驴单词薄", true, Some("CmdOrCtrl+Q"))?;
+
+    let app_menu = SubmenuBuilder::new(app, APP_NAME)
+        .item(&about_app)
+        .separator()
+        .item(&preferences)
+        .separator()
+        .item(&quit_app)
+        .build()?;
+
+    // 学习菜单
+    let grade_menu = SubmenuBuilder::new(app, "评分")
+        .item(&actions::menu_item(app, AppAction::Grade1)?)
+        .item(&actions::menu_item(app, AppAction::Grade2)?)
+        .item(&actions::menu_item(app, AppAction::Grade3)?)
+        .item(&actions::menu_item(app, AppAction::Grade4)?)
+        .build()?;
+
+    let auto_switch = check_item(app, AppAction::ToggleAutoSwitch, settings.auto_switch)?;
+    let refresh_words = MenuItem::with_id(app, "refresh_words", "刷新单词库", true, Some("CmdOrCtrl+R"))?;
+
+    let learning_menu = SubmenuBuilder::new(app, "学习")
+        .item(&actions::menu_item(app, AppAction::NextWord)?)
+        .item(&actions::menu_item(app, AppAction::PrevWord)?)
+        .separator()
+        .item(&actions::menu_item(app, AppAction::RevealAnswer)?)
+        .item(&grade_menu)
+        .item(&actions::menu_item(app, AppAction::MarkKnown)?)
+        .separator()
+        .item(&actions::menu_item(app, AppAction::SpeakWord)?)
+        .item(&actions::menu_item(app, AppAction::SpeakExample)?)
+        .separator()
+        .item(&auto_switch)
+        .separator()
+        .item(&wordbook_menu(app, &settings.active_wordbook)?)
+        .item(&refresh_words)
+        .build()?;
+
+    // 工具菜单
+    let always_on_top = check_item(app, AppAction::ToggleAlwaysOnTop, settings.always_on_top)?;
+    let open_settings = MenuItem::with_id(app, "open_settings", "设置", true, Some("CmdOrCtrl+Comma"))?;
+    let toggle_stats = MenuItem::with_id(app, "toggle_stats", "学习统计", true, Some("CmdOrCtrl+S"))?;
+    let theme_menu = MenuItem::with_id(app, "theme_menu", "主题切换", true, None::<&str>)?;
+
+    let tools_menu = SubmenuBuilder::new(app, "工具")
+        .item(&actions::menu_item(app, AppAction::OpenSearch)?)
+        .item(&always_on_top)
+        .separator()
+        .item(&open_settings)
+        .item(&toggle_stats)
+        .separator()
+        .item(&theme_menu)
+        .build()?;
+
+    MenuBuilder::new(app)
+        .items(&[&app_menu, &learning_menu, &tools_menu])
+        .build()
+}
+
+// 开关类操作显示为勾选项，点击后仍由操作注册表处理，设置变化后重建菜单同步勾选状态
+fn check_item(app: &AppHandle, action: AppAction, checked: bool) -> tauri::Result<CheckMenuItem<Wry>> {
+    CheckMenuItem::with_id(app, action.id(), action.label(), true, checked, None::<&str>)
+}
+
+fn wordbook_menu(app: &AppHandle, active: &str) -> tauri::Result<Submenu<Wry>> {
+    let names = wordbook::list_wordbooks().unwrap_or_else(|e| {
+        log::warn!("读取单词本列表失败: {}", e);
+        vec![active.to_string()]
+    });
+
+    let mut builder = SubmenuBuilder::new(app, "单词本");
+    for name in names {
+        let label = name.trim_end_matches(".xml").to_string();
+        let id = format!("{}{}", WORDBOOK_PREFIX, name);
+        let item = CheckMenuItem::with_id(app, id, label, true, name == active, None::<&str>)?;
+        builder = builder.item(&item);
+    }
+    builder.build()
+}
+
+fn on_menu_event(app: &AppHandle, id: &str) {
+    // 与操作同名的菜单项（包括托盘菜单）统一由操作注册表处理
+    if let Some(action) = AppAction::from_id(id) {
+        actions::trigger(app, action);
+        return;
+    }
+
+    if let Some(name) = id.strip_prefix(WORDBOOK_PREFIX) {
+        let app = app.clone();
+        let name = name.to_string();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = wordbook::switch_wordbook(&app, &name).await {
+                log::warn!("切换单词本失败: {}", e);
+                // 切换失败时恢复菜单中的勾选状态
+                if let Ok(current) = config::load_settings().await {
+                    let _ = rebuild(&app, &current);
+                }
+            }
+        });
+        return;
+    }
+
+    match id {
+        "about" => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = show_about(&app).await {
+                    log::warn!("显示关于对话框失败: {}", e);
+                }
+            });
+        }
+        "preferences" | "open_settings" => {
+            if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+                let _ = window.show();
+                let _ = window.set_focus();
+                // 发送事件到前端打开设置面板
+                let _ = window.emit("open-settings", ());
+            }
+        }
+        "quit_app" => app.exit(0),
+        "refresh_words" => {
+            emit_to_main(app, "menu-refresh-words");
+            // 顺便刷新单词本列表，新放入 vocabulary 目录的文件会出现在菜单中
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Ok(current) = config::load_settings().await {
+                    if let Err(e) = rebuild(&app, &current) {
+                        log::warn!("重建应用菜单失败: {}", e);
+                    }
+                }
+            });
+        }
+        "toggle_stats" => emit_to_main(app, "menu-toggle-stats"),
+        "theme_menu" => emit_to_main(app, "menu-theme-switch"),
+        // 托盘专属菜单项由 services::tray 处理
+        id if id.starts_with("tray_") => {}
+        _ => log::debug!("未处理的菜单事件: {}", id),
+    }
+}
+
+fn emit_to_main(app: &AppHandle, event: &str) {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+        let _ = window.emit(event, ());
+    }
+}
+
+/// 关于对话框：版本、数据目录和当前单词本统计
+async fn show_about(app: &AppHandle) -> Result<()> {
+    let version = app.package_info().version.to_string();
+    let info = config::get_app_data_info().await?;
+    let stats = wordbook::stats(app).await?;
+
+    let path = |key: &str| info[key].as_str().unwrap_or("-").to_string();
+    let message = format!(
+        "版本: {}\n\n数据目录: {}\n配置文件: {}\n单词本目录: {}\n\n当前单词本: {}\n单词数: {}，标签: {}\n已学习: {}，已掌握: {}",
+        version,
+        path("data_directory"),
+        path("config_file"),
+        path("vocabulary_directory"),
+        stats.name,
+        stats.total_words,
+        stats.tag_count,
+        stats.learned_words,
+        stats.mastered_words,
+    );
+
+    app.dialog()
+        .message(message)
+        .title(format!("关于{}", APP_NAME))
+        .kind(MessageDialogKind::Info)
+        .show(|_| {});
+    Ok(())
+}
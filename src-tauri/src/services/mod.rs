@@ -1,3 +1,24 @@
 pub mod xml_parser;
 pub mod learning;
-pub mod tts; 
\ No newline at end of file
+pub mod tts;
+pub mod audio;
+pub mod pronunciation;
+pub mod speech_queue;
+pub mod wordbook;
+pub mod duplicates;
+pub mod journal;
+pub mod settings;
+pub mod window;
+pub mod tray;
+pub mod file_watcher;
+pub mod hotkeys;
+pub mod actions;
+pub mod current_word;
+pub mod auto_switch;
+pub mod progress;
+pub mod review_log;
+pub mod sessions;
+pub mod analytics;
+pub mod reminders;
+pub mod achievements;
+pub mod menu;
@@ -0,0 +1,162 @@
+use crate::models::{ReviewRecord, WordProgress};
+use crate::services::settings::{self, SettingsChange, SettingsSection};
+use crate::services::{achievements, current_word, journal, review_log, sessions, tray};
+use crate::utils::{config, stores};
+use anyhow::Result;
+use chrono::Local;
+use std::sync::atomic::{AtomicU32, Ordering};
+use tauri::{AppHandle, Emitter};
+
+pub const PROGRESS_STORE: &str = "user_progress.json";
+pub const STATS_STORE: &str = "learning_stats.json";
+
+// 设置中的每日目标，托盘菜单等同步代码从这里读取，设置变更时更新
+static DAILY_GOAL: AtomicU32 = AtomicU32::new(20);
+
+/// 今日已学习的单词数和每日目标
+#[derive(Debug, Clone, Copy)]
+pub struct DailyProgress {
+    pub studied: u32,
+    pub goal: u32,
+}
+
+/// 记录一次复习结果，前端评分和托盘快速评分共用，同时追加到复习记录
+///
+/// 用时从单词显示开始计算；保存后发送 `progress-updated` 事件、刷新托盘菜单中的今日进度并检查成就。
+pub fn record_review(
+    app: &AppHandle,
+    word_id: &str,
+    progress: u8,
+    mastery_level: u8,
+    is_correct: bool,
+) -> Result<WordProgress> {
+    let time_spent = current_word::take_time_spent(word_id);
+    let store = stores::open(app, PROGRESS_STORE)?;
+
+    let progress_key = format!("word_{}", word_id);
+    let before = store.get(&progress_key);
+    let existing = before
+        .clone()
+        .and_then(|existing| serde_json::from_value::<WordProgress>(existing).ok());
+    let previous_review = existing.as_ref().map(|p| p.last_review);
+    let mut word_progress = existing
+        .unwrap_or_else(|| WordProgress {
+            word_id: word_id.to_string(),
+            progress,
+            mastery_level,
+            review_count: 0,
+            last_review: chrono::Utc::now(),
+            correct_count: 0,
+            incorrect_count: 0,
+            total_time_spent: 0,
+        });
+
+    word_progress.progress = progress;
+    word_progress.mastery_level = mastery_level;
+    word_progress.review_count += 1;
+    word_progress.last_review = chrono::Utc::now();
+    word_progress.total_time_spent += time_spent;
+
+    if is_correct {
+        word_progress.correct_count += 1;
+    } else {
+        word_progress.incorrect_count += 1;
+    }
+
+    let after = serde_json::to_value(&word_progress)?;
+    store.set(&progress_key, after.clone());
+    stores::save(app, PROGRESS_STORE)?;
+
+    let record = ReviewRecord {
+        id: uuid::Uuid::new_v4().to_string(),
+        word_id: word_id.to_string(),
+        session_id: sessions::active_id(),
+        reviewed_at: word_progress.last_review,
+        previous_review,
+        is_correct,
+        time_spent,
+        progress,
+        mastery_level,
+    };
+    review_log::append(app, &record)?;
+
+    // 误点评分可以撤销：恢复原来的学习进度并删除这条复习记录
+    let label = match current_word::get().filter(|word| word.id == word_id) {
+        Some(word) => format!("评分 {}", word.word),
+        None => format!("评分 {}", word_id),
+    };
+    journal::record_review(
+        &label,
+        vec![
+            journal::Change::StoreEntry {
+                store: PROGRESS_STORE.to_string(),
+                key: progress_key,
+                before,
+                after: Some(after),
+            },
+            journal::Change::ReviewRecord { record },
+        ],
+    );
+
+    log::info!("✅ 单词 {} 学习进度已保存: 进度={}, 掌握度={}", word_id, progress, mastery_level);
+
+    if let Err(e) = app.emit("progress-updated", &word_progress) {
+        log::warn!("发送学习进度事件失败: {}", e);
+    }
+    tray::refresh(app);
+    achievements::spawn_evaluate(app, achievements::Trigger::Review { at: word_progress.last_review });
+
+    Ok(word_progress)
+}
+
+/// 读取所有单词的学习进度
+pub fn load_all(app: &AppHandle) -> Result<Vec<WordProgress>> {
+    let store = stores::open(app, PROGRESS_STORE)?;
+
+    Ok(store
+        .entries()
+        .into_iter()
+        .filter(|(key, _)| key.starts_with("word_"))
+        .filter_map(|(_, value)| serde_json::from_value::<WordProgress>(value).ok())
+        .collect())
+}
+
+/// 订阅设置变更，每日目标修改后刷新托盘中的今日进度
+pub fn register_settings_subscriber() {
+    settings::subscribe(on_settings_changed);
+}
+
+fn on_settings_changed(app: &AppHandle, change: &SettingsChange) {
+    if change.touches(SettingsSection::Learning) {
+        DAILY_GOAL.store(change.new.learning.daily_goal, Ordering::Relaxed);
+        tray::refresh(app);
+    }
+}
+
+/// 启动时读取设置中的每日目标
+pub async fn init(app: &AppHandle) -> Result<()> {
+    let settings = config::load_settings().await?;
+    DAILY_GOAL.store(settings.learning.daily_goal, Ordering::Relaxed);
+    tray::refresh(app);
+    Ok(())
+}
+
+pub fn daily_goal() -> u32 {
+    DAILY_GOAL.load(Ordering::Relaxed)
+}
+
+/// 今日复习过的单词数（按本地日期）
+pub fn studied_today(progresses: &[WordProgress]) -> u32 {
+    let today = Local::now().date_naive();
+    progresses
+        .iter()
+        .filter(|p| p.last_review.with_timezone(&Local).date_naive() == today)
+        .count() as u32
+}
+
+pub fn today(app: &AppHandle) -> Result<DailyProgress> {
+    Ok(DailyProgress {
+        studied: studied_today(&load_all(app)?),
+        goal: daily_goal(),
+    })
+}
@@ -0,0 +1,383 @@
+use crate::models::{PronunciationBackendKind, SpeechCommand, TtsConfig};
+use crate::services::{audio, tts};
+use anyhow::{Result, anyhow};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+// 本地合成命令的最长运行时间，超时后结束进程
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(20);
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(50);
+// 错误信息中保留的合成程序输出，只取最后一段
+const MAX_STDERR_CHARS: usize = 500;
+// 合成结果缓存的文件数上限，超出时删除最早生成的文件
+const MAX_CACHED_FILES: usize = 500;
+
+// 各发音方式最近一次失败的原因，成功后清除
+static LAST_ERRORS: Lazy<Mutex<HashMap<PronunciationBackendKind, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    Chinese,
+}
+
+/// 一段待朗读的内容
+#[derive(Debug, Clone)]
+pub struct SpeechRequest {
+    pub text: String,
+    pub language: Language,
+    pub voice_id: String, // 朗读步骤指定的语音，空字符串表示使用设置
+    pub headword: bool,   // 是否为单词本身，发音文件只覆盖单词
+    pub generation: u64,  // 所属朗读任务的代数，被打断后不再合成和播放
+}
+
+impl SpeechRequest {
+    pub fn headword(word: &str, generation: u64) -> Self {
+        Self {
+            text: word.to_string(),
+            language: Language::English,
+            voice_id: String::new(),
+            headword: true,
+            generation,
+        }
+    }
+
+    fn is_interrupted(&self) -> bool {
+        !tts::is_current(self.generation)
+    }
+}
+
+/// 一种发音方式；`speak` 开始播放后即返回，播放状态通过 `is_speaking` 查询
+pub trait PronunciationBackend: Send + Sync {
+    fn kind(&self) -> PronunciationBackendKind;
+
+    /// 能否朗读这段内容，不能时直接跳到下一种方式，不记为失败
+    fn supports(&self, request: &SpeechRequest, config: &TtsConfig) -> bool;
+
+    fn speak(&self, request: &SpeechRequest, config: &TtsConfig) -> Result<()>;
+
+    fn stop(&self);
+
+    fn is_speaking(&self) -> Result<bool>;
+}
+
+/// 发音方式的状态，供设置界面显示
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendStatus {
+    pub kind: PronunciationBackendKind,
+    pub enabled: bool,    // 是否在设置的优先级列表中
+    pub configured: bool, // 本地命令需要先配置程序
+    pub last_error: Option<String>,
+}
+
+struct AudioFileBackend;
+struct CommandBackend;
+
+static BACKENDS: Lazy<Vec<Box<dyn PronunciationBackend>>> = Lazy::new(|| {
+    vec![
+        Box::new(AudioFileBackend),
+        Box::new(tts::SystemTtsBackend),
+        Box::new(CommandBackend),
+    ]
+});
+
+fn backend(kind: PronunciationBackendKind) -> &'static dyn PronunciationBackend {
+    BACKENDS
+        .iter()
+        .find(|backend| backend.kind() == kind)
+        .map(|backend| backend.as_ref())
+        .expect("每种发音方式都有对应的实现")
+}
+
+/// 按设置中的优先级依次尝试各发音方式，返回实际使用的方式
+///
+/// 本地命令可能运行数秒，在阻塞线程中执行；所有方式都失败时返回汇总的错误。
+/// 朗读在开始播放前被打断时返回 None。
+pub async fn speak(request: SpeechRequest, config: TtsConfig) -> Result<Option<PronunciationBackendKind>> {
+    tokio::task::spawn_blocking(move || speak_blocking(&request, &config)).await?
+}
+
+fn speak_blocking(request: &SpeechRequest, config: &TtsConfig) -> Result<Option<PronunciationBackendKind>> {
+    let mut failures = Vec::new();
+
+    for kind in &config.backends {
+        if request.is_interrupted() {
+            log::debug!("🔇 朗读已被打断，不再播放: {}", request.text);
+            return Ok(None);
+        }
+
+        let backend = backend(*kind);
+        if !backend.supports(request, config) {
+            continue;
+        }
+
+        match backend.speak(request, config) {
+            Ok(()) => {
+                LAST_ERRORS.lock().unwrap().remove(kind);
+                if !failures.is_empty() {
+                    log::info!("🔈 已改用 {:?} 朗读: {}", kind, request.text);
+                }
+                return Ok(Some(*kind));
+            }
+            Err(e) => {
+                log::warn!("🔇 {:?} 朗读失败，尝试下一种发音方式: {}", kind, e);
+                LAST_ERRORS.lock().unwrap().insert(*kind, e.to_string());
+                failures.push(format!("{:?}: {}", kind, e));
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        Err(anyhow!("没有可用的发音方式: {}", request.text))
+    } else {
+        Err(anyhow!("所有发音方式均失败: {}", failures.join("; ")))
+    }
+}
+
+/// 停止所有发音方式的播放
+pub fn stop() {
+    for backend in BACKENDS.iter() {
+        backend.stop();
+    }
+}
+
+/// 任一发音方式正在播放时返回 true；都无法查询状态时返回错误
+pub fn is_speaking() -> Result<bool> {
+    let mut last_error = None;
+    for backend in BACKENDS.iter() {
+        match backend.is_speaking() {
+            Ok(true) => return Ok(true),
+            Ok(false) => {}
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    // 系统语音不支持查询时无法确定是否还在朗读，交给调用方估算
+    match last_error {
+        Some(e) => Err(e),
+        None => Ok(false),
+    }
+}
+
+/// 所有发音方式的状态，按设置中的优先级排列，未启用的排在最后
+pub fn status(config: &TtsConfig) -> Vec<BackendStatus> {
+    let errors = LAST_ERRORS.lock().unwrap();
+
+    let mut kinds = config.backends.clone();
+    kinds.extend(BACKENDS.iter().map(|backend| backend.kind()).filter(|kind| !config.backends.contains(kind)));
+
+    kinds
+        .into_iter()
+        .map(|kind| BackendStatus {
+            kind,
+            enabled: config.backends.contains(&kind),
+            configured: kind != PronunciationBackendKind::Command || is_configured(&config.command),
+            last_error: errors.get(&kind).cloned(),
+        })
+        .collect()
+}
+
+impl PronunciationBackend for AudioFileBackend {
+    fn kind(&self) -> PronunciationBackendKind {
+        PronunciationBackendKind::AudioFile
+    }
+
+    fn supports(&self, request: &SpeechRequest, _config: &TtsConfig) -> bool {
+        request.headword && audio::find_audio_file(&request.text).is_some()
+    }
+
+    fn speak(&self, request: &SpeechRequest, config: &TtsConfig) -> Result<()> {
+        let path = audio::find_audio_file(&request.text).ok_or_else(|| anyhow!("没有发音文件: {}", request.text))?;
+        audio::play_file(&path, config.volume)?;
+        log::debug!("🎵 播放发音文件: {:?}", path);
+        Ok(())
+    }
+
+    fn stop(&self) {
+        audio::stop();
+    }
+
+    fn is_speaking(&self) -> Result<bool> {
+        Ok(audio::is_playing())
+    }
+}
+
+// 本地命令合成的语音同样交给音频播放线程播放，因此停止和状态查询与发音文件共用
+impl PronunciationBackend for CommandBackend {
+    fn kind(&self) -> PronunciationBackendKind {
+        PronunciationBackendKind::Command
+    }
+
+    fn supports(&self, _request: &SpeechRequest, config: &TtsConfig) -> bool {
+        is_configured(&config.command)
+    }
+
+    fn speak(&self, request: &SpeechRequest, config: &TtsConfig) -> Result<()> {
+        let output = synthesize(&config.command, request)?;
+        // 合成期间朗读被打断（切换单词、停止朗读）时不再播放过时的内容
+        match output {
+            Some(output) if !request.is_interrupted() => audio::play_file(&output, config.volume),
+            _ => {
+                log::debug!("🔇 朗读已被打断，跳过合成结果: {}", request.text);
+                Ok(())
+            }
+        }
+    }
+
+    fn stop(&self) {
+        audio::stop();
+    }
+
+    fn is_speaking(&self) -> Result<bool> {
+        Ok(audio::is_playing())
+    }
+}
+
+fn is_configured(command: &SpeechCommand) -> bool {
+    !command.program.trim().is_empty()
+}
+
+/// 运行本地命令合成语音，同样的命令和内容只合成一次
+///
+/// 朗读被打断时结束合成进程并返回 None。
+fn synthesize(command: &SpeechCommand, request: &SpeechRequest) -> Result<Option<PathBuf>> {
+    let output = cache_path(command, request)?;
+    if output.is_file() {
+        return Ok(Some(output));
+    }
+
+    let output_arg = output.to_string_lossy();
+    let uses_text_arg = command.args.iter().any(|arg| arg.contains("{text}"));
+    let args: Vec<String> = command
+        .args
+        .iter()
+        .map(|arg| {
+            arg.replace("{text}", &request.text)
+                .replace("{output}", &output_arg)
+                .replace("{voice}", &request.voice_id)
+        })
+        .collect();
+
+    let mut child = Command::new(command.program.trim())
+        .args(&args)
+        .stdin(if uses_text_arg { Stdio::null() } else { Stdio::piped() })
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("无法启动语音合成程序 {}: {}", command.program, e))?;
+
+    // 边运行边读取错误输出，输出较多的程序（如 piper 的进度日志）写满管道后会一直阻塞
+    let stderr_reader = child.stderr.take().map(|mut pipe| {
+        std::thread::spawn(move || {
+            let mut buffer = Vec::new();
+            let _ = pipe.read_to_end(&mut buffer);
+            String::from_utf8_lossy(&buffer).into_owned()
+        })
+    });
+
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "{}", request.text)?;
+    }
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if request.is_interrupted() {
+            let _ = child.kill();
+            let _ = child.wait();
+            let _ = std::fs::remove_file(&output);
+            log::debug!("🔇 朗读已被打断，结束语音合成: {}", request.text);
+            return Ok(None);
+        }
+        if started.elapsed() > COMMAND_TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            let _ = std::fs::remove_file(&output);
+            return Err(anyhow!("语音合成超时（{} 秒）", COMMAND_TIMEOUT.as_secs()));
+        }
+        std::thread::sleep(COMMAND_POLL_INTERVAL);
+    };
+
+    let stderr = stderr_reader
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default();
+
+    if !status.success() {
+        let _ = std::fs::remove_file(&output);
+        return Err(anyhow!("语音合成程序退出 ({}): {}", status, tail(stderr.trim(), MAX_STDERR_CHARS)));
+    }
+    if !output.is_file() {
+        return Err(anyhow!("语音合成程序没有生成文件 {:?}", output));
+    }
+
+    log::debug!("🗣️ 本地命令合成语音: {}", request.text);
+    if let Some(directory) = output.parent() {
+        prune_cache(directory);
+    }
+    Ok(Some(output))
+}
+
+fn tail(text: &str, max_chars: usize) -> &str {
+    let skip = text.chars().count().saturating_sub(max_chars);
+    match text.char_indices().nth(skip) {
+        Some((index, _)) => &text[index..],
+        None => text,
+    }
+}
+
+// 只保留最近生成的 MAX_CACHED_FILES 个文件，避免临时目录无限增长
+fn prune_cache(directory: &Path) {
+    let entries = match std::fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::warn!("读取语音缓存目录失败: {}", e);
+            return;
+        }
+    };
+
+    let mut files: Vec<(SystemTime, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            metadata.is_file().then(|| (metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH), entry.path()))
+        })
+        .collect();
+    if files.len() <= MAX_CACHED_FILES {
+        return;
+    }
+
+    files.sort_by(|a, b| b.0.cmp(&a.0));
+    let removed = files.len() - MAX_CACHED_FILES;
+    for (_, path) in files.into_iter().skip(MAX_CACHED_FILES) {
+        let _ = std::fs::remove_file(path);
+    }
+    log::debug!("🧹 已清理 {} 个语音缓存文件", removed);
+}
+
+// 合成结果放在临时目录，文件名由命令和内容的哈希决定，修改命令后自然不再命中旧文件
+fn cache_path(command: &SpeechCommand, request: &SpeechRequest) -> Result<PathBuf> {
+    let directory = std::env::temp_dir().join("word-pony-speech");
+    std::fs::create_dir_all(&directory)?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(command.program.as_bytes());
+    for arg in &command.args {
+        hasher.update([0]);
+        hasher.update(arg.as_bytes());
+    }
+    hasher.update([0]);
+    hasher.update(request.voice_id.as_bytes());
+    hasher.update([0]);
+    hasher.update(request.text.as_bytes());
+
+    Ok(directory.join(format!("{:x}.wav", hasher.finalize())))
+}
@@ -0,0 +1,251 @@
+use crate::models::{AppAction, NotificationConfig, Settings};
+use crate::services::settings::{self, SettingsChange, SettingsSection};
+use crate::services::{progress, tray, xml_parser};
+use crate::utils::config;
+use anyhow::Result;
+use chrono::{Datelike, Local, Utc};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+// 检查是否需要提醒的间隔，提醒间隔本身以分钟计
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 提醒的原因，决定通知内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReminderReason {
+    DailyGoal { studied: u32, goal: u32 },
+    ReviewsDue { count: u32 },
+}
+
+/// 提醒的当前状态，供设置界面显示
+#[derive(Debug, Clone, Serialize)]
+pub struct ReminderState {
+    pub enabled: bool,
+    pub pending: bool,       // 已发送提醒但还没开始学习，托盘中显示「开始学习」
+    pub silenced: bool,      // 当前处于免打扰时段或临时免打扰
+    pub next_in: Option<u64>, // 距离下一次检查提醒的秒数
+}
+
+struct Scheduler {
+    config: NotificationConfig,
+    last_reminder: Instant, // 上一次提醒（或启动）的时间，间隔从这里开始计算
+    pending: bool,
+}
+
+static SCHEDULER: Lazy<Mutex<Scheduler>> = Lazy::new(|| {
+    Mutex::new(Scheduler {
+        config: NotificationConfig::default(),
+        last_reminder: Instant::now(),
+        pending: false,
+    })
+});
+
+/// 启动后台提醒任务，按设置的间隔在未完成每日目标或有待复习单词时发送通知
+pub async fn start(app: &AppHandle) -> Result<()> {
+    let current = config::load_settings().await?;
+    apply(&current);
+    settings::subscribe(on_settings_changed);
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut ticks = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            ticks.tick().await;
+            if let Err(e) = check(&app).await {
+                log::warn!("检查学习提醒失败: {}", e);
+            }
+        }
+    });
+
+    log::info!("⏰ 学习提醒已启动");
+    Ok(())
+}
+
+fn apply(settings: &Settings) {
+    let mut scheduler = SCHEDULER.lock().unwrap();
+    scheduler.config = settings.notification.clone();
+    // 修改提醒间隔后从现在开始重新计时
+    scheduler.last_reminder = Instant::now();
+}
+
+fn on_settings_changed(app: &AppHandle, change: &SettingsChange) {
+    if !change.touches(SettingsSection::Notification) {
+        return;
+    }
+
+    apply(&change.new);
+    if !is_enabled(&change.new.notification) {
+        dismiss(app);
+    }
+}
+
+pub fn get_state() -> ReminderState {
+    let scheduler = SCHEDULER.lock().unwrap();
+    let enabled = is_enabled(&scheduler.config);
+    let interval = Duration::from_secs(scheduler.config.reminder_interval as u64 * 60);
+
+    ReminderState {
+        enabled,
+        pending: scheduler.pending,
+        silenced: is_silenced(&scheduler.config),
+        next_in: enabled.then(|| interval.saturating_sub(scheduler.last_reminder.elapsed()).as_secs()),
+    }
+}
+
+/// 是否有未处理的提醒
+pub fn is_pending() -> bool {
+    SCHEDULER.lock().unwrap().pending
+}
+
+/// 用户开始学习后清除提醒，托盘中不再显示「开始学习」
+pub fn dismiss(app: &AppHandle) {
+    let was_pending = std::mem::replace(&mut SCHEDULER.lock().unwrap().pending, false);
+    if was_pending {
+        tray::refresh(app);
+    }
+}
+
+/// 临时免打扰指定分钟数，None 表示取消免打扰
+pub async fn set_do_not_disturb(app: &AppHandle, minutes: Option<u32>) -> Result<()> {
+    let until = minutes.map(|minutes| Utc::now() + chrono::Duration::minutes(minutes as i64));
+
+    settings::apply_patch(app, serde_json::json!({ "notification": { "do_not_disturb_until": until } }))
+        .await
+        .map_err(|errors| anyhow::anyhow!("{:?}", errors))?;
+
+    match until {
+        Some(until) => log::info!("🔕 免打扰至 {}", until.with_timezone(&Local).format("%H:%M")),
+        None => log::info!("🔔 已取消免打扰"),
+    }
+    Ok(())
+}
+
+/// 立即发送一次学习提醒（设置界面测试用），不检查间隔和免打扰
+pub async fn send_now(app: &AppHandle) -> Result<()> {
+    let reason = match reason(app).await? {
+        Some(reason) => reason,
+        None => {
+            let today = progress::today(app)?;
+            ReminderReason::DailyGoal { studied: today.studied, goal: today.goal }
+        }
+    };
+    notify(app, reason)
+}
+
+async fn check(app: &AppHandle) -> Result<()> {
+    let (config, due) = {
+        let scheduler = SCHEDULER.lock().unwrap();
+        let interval = Duration::from_secs(scheduler.config.reminder_interval as u64 * 60);
+        (scheduler.config.clone(), scheduler.last_reminder.elapsed() >= interval)
+    };
+
+    if !is_enabled(&config) || !due || is_silenced(&config) {
+        return Ok(());
+    }
+
+    // 最近一个提醒间隔内学习过就不打扰，从现在开始重新计时
+    if studied_within(app, config.reminder_interval)? {
+        SCHEDULER.lock().unwrap().last_reminder = Instant::now();
+        dismiss(app);
+        return Ok(());
+    }
+
+    let Some(reason) = reason(app).await? else {
+        dismiss(app);
+        return Ok(());
+    };
+
+    SCHEDULER.lock().unwrap().last_reminder = Instant::now();
+    notify(app, reason)
+}
+
+fn is_enabled(config: &NotificationConfig) -> bool {
+    config.enable_notifications && config.study_reminders
+}
+
+/// 免打扰时段、非提醒日或临时免打扰期间不发送提醒
+fn is_silenced(config: &NotificationConfig) -> bool {
+    if config.do_not_disturb_until.is_some_and(|until| Utc::now() < until) {
+        return true;
+    }
+
+    let now = Local::now();
+    let weekday = now.weekday().num_days_from_sunday();
+    if !config.reminder_weekdays.contains(&weekday) {
+        return true;
+    }
+
+    config.quiet_hours.contains(now.time())
+}
+
+fn studied_within(app: &AppHandle, minutes: u32) -> Result<bool> {
+    let since = Utc::now() - chrono::Duration::minutes(minutes as i64);
+    Ok(progress::load_all(app)?.iter().any(|p| p.last_review > since))
+}
+
+/// 未完成每日目标优先提醒，目标完成后仍有待复习单词时提醒复习
+async fn reason(app: &AppHandle) -> Result<Option<ReminderReason>> {
+    let today = progress::today(app)?;
+    if today.studied < today.goal {
+        return Ok(Some(ReminderReason::DailyGoal {
+            studied: today.studied,
+            goal: today.goal,
+        }));
+    }
+
+    let due = due_reviews(app).await?;
+    if due > 0 {
+        return Ok(Some(ReminderReason::ReviewsDue { count: due }));
+    }
+
+    Ok(None)
+}
+
+/// 当前单词本中学过且到了复习时间的单词数，按保存的学习进度计算
+async fn due_reviews(app: &AppHandle) -> Result<u32> {
+    let word_ids: HashSet<String> = xml_parser::load_words(None)
+        .await?
+        .into_iter()
+        .map(|word| word.id)
+        .collect();
+
+    let now = Utc::now();
+    Ok(progress::load_all(app)?
+        .iter()
+        .filter(|p| word_ids.contains(&p.word_id) && p.is_due(now))
+        .count() as u32)
+}
+
+fn notify(app: &AppHandle, reason: ReminderReason) -> Result<()> {
+    let sound = SCHEDULER.lock().unwrap().config.sound_enabled;
+
+    let body = match reason {
+        ReminderReason::DailyGoal { studied, goal } if studied >= goal => {
+            format!("今日目标已完成 {}/{}，再复习几个巩固一下", studied, goal)
+        }
+        ReminderReason::DailyGoal { studied, goal } => {
+            format!("今日进度 {}/{}，还差 {} 个单词", studied, goal, goal - studied)
+        }
+        ReminderReason::ReviewsDue { count } => format!("有 {} 个单词到了复习时间", count),
+    };
+    // 桌面通知不支持点击回调，通过托盘菜单中的「开始学习」打开窗口
+    let body = format!("{}\n点击托盘菜单中的「{}」开始", body, AppAction::StudyNow.label());
+
+    let mut builder = app.notification().builder().title("📚 该学习单词啦").body(body);
+    if sound {
+        builder = builder.sound("default");
+    }
+    builder.show()?;
+
+    SCHEDULER.lock().unwrap().pending = true;
+    tray::refresh(app);
+
+    log::info!("⏰ 已发送学习提醒: {:?}", reason);
+    Ok(())
+}
@@ -0,0 +1,500 @@
+use crate::models::{DateRange, ReviewRecord, StudySession};
+use crate::utils::{atomic_file, config, stores};
+use anyhow::{Result, anyhow};
+use chrono::{Local, NaiveDate, Utc};
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+/// 旧版本把复习记录保存在这个 store 中，首次读写时迁移到按天分文件的日志
+pub const LEGACY_REVIEW_LOG_STORE: &str = "review_log.json";
+
+// 每天一个 JSON Lines 文件（按本地日期），评分时只追加一行，按日期范围查询时只读取相关的文件
+const DAY_FILE_EXTENSION: &str = "jsonl";
+// 清空复习记录时保留的归档数量，供撤销使用
+const MAX_ARCHIVES: usize = 10;
+
+// 串行化追加、读取和归档，避免读到写了一半的行；值表示是否已迁移过旧格式的 store
+static LOCK: Lazy<Mutex<bool>> = Lazy::new(|| Mutex::new(false));
+
+/// 追加一条复习记录
+pub fn append(app: &AppHandle, record: &ReviewRecord) -> Result<()> {
+    let mut migrated = LOCK.lock().unwrap();
+    migrate_legacy_store(app, &mut migrated)?;
+    append_records(&log_directory()?, std::slice::from_ref(record))
+}
+
+/// 删除一条复习记录（撤销评分时使用），记录不存在时不做处理
+pub fn remove(app: &AppHandle, record: &ReviewRecord) -> Result<()> {
+    let mut migrated = LOCK.lock().unwrap();
+    migrate_legacy_store(app, &mut migrated)?;
+
+    let path = day_file_path(&log_directory()?, record.reviewed_at.with_timezone(&Local).date_naive());
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let records = read_day_file(&path)?;
+    let remaining: Vec<&ReviewRecord> = records.iter().filter(|existing| existing.id != record.id).collect();
+    if remaining.len() == records.len() {
+        return Ok(());
+    }
+
+    let mut content = String::new();
+    for existing in remaining {
+        content.push_str(&serde_json::to_string(existing)?);
+        content.push('\n');
+    }
+    atomic_file::write_atomic(&path, content)
+}
+
+/// 读取日期范围内的复习记录，按时间先后排序
+pub fn load(app: &AppHandle, range: DateRange) -> Result<Vec<ReviewRecord>> {
+    let mut migrated = LOCK.lock().unwrap();
+    migrate_legacy_store(app, &mut migrated)?;
+
+    let mut records = Vec::new();
+    for (date, path) in day_files(&log_directory()?)? {
+        if range.contains_date(date) {
+            records.extend(read_day_file(&path)?);
+        }
+    }
+    records.retain(|record| range.contains(record.reviewed_at));
+    records.sort_by_key(|record| record.reviewed_at);
+
+    Ok(records)
+}
+
+/// 某个学习会话中的复习记录
+///
+/// 记录按日期存放，只需读取会话开始到结束（进行中的会话到今天）之间的几天。
+pub fn load_session(app: &AppHandle, session: &StudySession) -> Result<Vec<ReviewRecord>> {
+    let ended_at = session.ended_at.unwrap_or_else(Utc::now);
+    let range = DateRange::new(
+        Some(session.started_at.with_timezone(&Local).date_naive()),
+        Some(ended_at.with_timezone(&Local).date_naive()),
+    );
+
+    Ok(load(app, range)?
+        .into_iter()
+        .filter(|record| record.session_id.as_deref() == Some(session.session_id.as_str()))
+        .collect())
+}
+
+/// 导入复习记录，已存在的记录（按 id）跳过，返回新增的条数
+pub fn import(app: &AppHandle, records: Vec<ReviewRecord>) -> Result<usize> {
+    let mut migrated = LOCK.lock().unwrap();
+    migrate_legacy_store(app, &mut migrated)?;
+
+    let mut existing = HashSet::new();
+    for (_, path) in day_files(&log_directory()?)? {
+        existing.extend(read_day_file(&path)?.into_iter().map(|record| record.id));
+    }
+
+    let new_records: Vec<ReviewRecord> = records
+        .into_iter()
+        .filter(|record| existing.insert(record.id.clone()))
+        .collect();
+    append_records(&log_directory()?, &new_records)?;
+    Ok(new_records.len())
+}
+
+/// 新归档的名称，包含时间戳，按名称排序即按时间排序
+pub fn new_archive_name() -> String {
+    Local::now().format("%Y%m%d_%H%M%S_%3f").to_string()
+}
+
+/// 把当前的复习记录移到归档目录，之后的记录从空日志开始
+pub fn archive(app: &AppHandle, archive_id: &str) -> Result<()> {
+    let mut migrated = LOCK.lock().unwrap();
+    migrate_legacy_store(app, &mut migrated)?;
+
+    let directory = log_directory()?;
+    if !move_to_archive(&directory, &archive_directory()?.join(archive_id))? {
+        return Ok(());
+    }
+    prune_archives();
+
+    log::info!("🗄️ 复习记录已归档: {}", archive_id);
+    Ok(())
+}
+
+/// 恢复归档的复习记录，归档之后新增的记录保留在各自日期的末尾
+pub fn restore(archive_id: &str) -> Result<()> {
+    let _guard = LOCK.lock().unwrap();
+
+    let archive_directory = archive_directory()?.join(archive_id);
+    if !archive_directory.exists() {
+        return Err(anyhow!("复习记录归档不存在: {}", archive_id));
+    }
+    merge_archive(&archive_directory, &log_directory()?)?;
+
+    log::info!("📂 复习记录已从归档恢复: {}", archive_id);
+    Ok(())
+}
+
+// 把日志目录整个移为归档，返回是否有记录被归档；没有记录时同样留下空归档，撤销时按同样的方式恢复
+fn move_to_archive(directory: &Path, archive_directory: &Path) -> Result<bool> {
+    if !directory.exists() {
+        fs::create_dir_all(archive_directory)?;
+        return Ok(false);
+    }
+
+    fs::create_dir_all(archive_directory.parent().unwrap_or(archive_directory))?;
+    fs::rename(directory, archive_directory)
+        .map_err(|e| anyhow!("无法归档复习记录: {}", e))?;
+    Ok(true)
+}
+
+// 把归档的日志文件放回日志目录，同一天已有新记录时把归档的内容放在前面，然后删除归档
+fn merge_archive(archive_directory: &Path, directory: &Path) -> Result<()> {
+    fs::create_dir_all(directory)?;
+    for (_, archived) in day_files(archive_directory)? {
+        let Some(name) = archived.file_name() else {
+            continue;
+        };
+        let target = directory.join(name);
+        if target.exists() {
+            let mut content = fs::read(&archived)?;
+            // 归档中不完整的最后一行不能与新记录接在同一行
+            if content.last().is_some_and(|byte| *byte != b'\n') {
+                content.push(b'\n');
+            }
+            content.extend(fs::read(&target)?);
+            atomic_file::write_atomic(&target, content)?;
+            fs::remove_file(&archived)?;
+        } else {
+            fs::rename(&archived, &target)?;
+        }
+    }
+    fs::remove_dir_all(archive_directory)?;
+    Ok(())
+}
+
+fn log_directory() -> Result<PathBuf> {
+    config::get_data_directory().map(|dir| dir.join("user").join("review_log"))
+}
+
+fn archive_directory() -> Result<PathBuf> {
+    config::get_data_directory().map(|dir| dir.join("user").join("review_log_archive"))
+}
+
+fn append_records(directory: &Path, records: &[ReviewRecord]) -> Result<()> {
+    if records.is_empty() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(directory)
+        .map_err(|e| anyhow!("无法创建复习记录目录 {:?}: {}", directory, e))?;
+
+    let mut lines_by_day: Vec<(NaiveDate, String)> = Vec::new();
+    for record in records {
+        let date = record.reviewed_at.with_timezone(&Local).date_naive();
+        let line = serde_json::to_string(record)?;
+        match lines_by_day.iter_mut().find(|(day, _)| *day == date) {
+            Some((_, lines)) => {
+                lines.push_str(&line);
+                lines.push('\n');
+            }
+            None => lines_by_day.push((date, format!("{}\n", line))),
+        }
+    }
+
+    for (date, lines) in lines_by_day {
+        let path = day_file_path(directory, date);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| anyhow!("无法打开复习记录文件 {:?}: {}", path, e))?;
+        // 上次写入中断留下的半行不能与新记录接在同一行
+        if !ends_with_newline(&mut file)? {
+            file.write_all(b"\n")?;
+        }
+        file.write_all(lines.as_bytes())?;
+        file.sync_data()?;
+    }
+    Ok(())
+}
+
+fn ends_with_newline(file: &mut File) -> Result<bool> {
+    if file.metadata()?.len() == 0 {
+        return Ok(true);
+    }
+    let mut last = [0u8; 1];
+    file.seek(SeekFrom::End(-1))?;
+    file.read_exact(&mut last)?;
+    Ok(last[0] == b'\n')
+}
+
+fn day_file_path(directory: &Path, date: NaiveDate) -> PathBuf {
+    directory.join(format!("{}.{}", date.format("%Y-%m-%d"), DAY_FILE_EXTENSION))
+}
+
+/// 目录中的日志文件及其日期，按日期排序
+fn day_files(directory: &Path) -> Result<Vec<(NaiveDate, PathBuf)>> {
+    if !directory.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut files: Vec<(NaiveDate, PathBuf)> = fs::read_dir(directory)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == DAY_FILE_EXTENSION))
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_str()?;
+            let date = NaiveDate::parse_from_str(stem, "%Y-%m-%d").ok()?;
+            Some((date, path))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+// 写入中途崩溃可能留下不完整的最后一行，跳过无法解析的行
+fn read_day_file(path: &Path) -> Result<Vec<ReviewRecord>> {
+    let file = File::open(path).map_err(|e| anyhow!("无法读取复习记录文件 {:?}: {}", path, e))?;
+
+    let mut records = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<ReviewRecord>(&line) {
+            Ok(record) => records.push(record),
+            Err(e) => log::warn!("⚠️ 跳过无法解析的复习记录 {:?}: {}", path, e),
+        }
+    }
+    Ok(records)
+}
+
+// 旧版本的记录全部写入日志后从 store 中删除，store 文件保留为空
+fn migrate_legacy_store(app: &AppHandle, migrated: &mut bool) -> Result<()> {
+    if *migrated {
+        return Ok(());
+    }
+
+    let store = stores::open(app, LEGACY_REVIEW_LOG_STORE)?;
+    let legacy = legacy_records(store.entries());
+
+    if !legacy.is_empty() {
+        let records: Vec<ReviewRecord> = legacy.iter().map(|(_, record)| record.clone()).collect();
+        append_records(&log_directory()?, &records)?;
+        for (key, _) in &legacy {
+            store.delete(key);
+        }
+        stores::save(app, LEGACY_REVIEW_LOG_STORE)?;
+        log::info!("🔄 已迁移 {} 条复习记录到按天分文件的日志", legacy.len());
+    }
+
+    *migrated = true;
+    Ok(())
+}
+
+// 旧 store 中以 review_ 开头的条目，无法解析的跳过
+fn legacy_records(entries: Vec<(String, serde_json::Value)>) -> Vec<(String, ReviewRecord)> {
+    entries
+        .into_iter()
+        .filter(|(key, _)| key.starts_with("review_"))
+        .filter_map(|(key, value)| serde_json::from_value::<ReviewRecord>(value).ok().map(|record| (key, record)))
+        .collect()
+}
+
+// 只保留最近的几个归档，更早的重置已经无法撤销（撤销历史只在内存中，且有条数上限）
+fn prune_archives() {
+    let Ok(directory) = archive_directory() else {
+        return;
+    };
+    let Ok(entries) = fs::read_dir(&directory) else {
+        return;
+    };
+
+    let mut archives: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+
+    // 归档名包含时间戳，按名称排序即按时间排序
+    archives.sort();
+    let excess = archives.len().saturating_sub(MAX_ARCHIVES);
+    for path in archives.into_iter().take(excess) {
+        if let Err(e) = fs::remove_dir_all(&path) {
+            log::warn!("删除旧的复习记录归档失败: {:?} - {}", path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("word-pony-review-log-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // 取当天中午的时间，换算成本地日期时不会落到相邻的一天
+    fn record(id: &str, day: &str) -> ReviewRecord {
+        ReviewRecord {
+            id: id.to_string(),
+            word_id: format!("word_{}", id),
+            session_id: None,
+            reviewed_at: format!("{}T12:00:00Z", day).parse().unwrap(),
+            previous_review: None,
+            is_correct: true,
+            time_spent: 3,
+            progress: 50,
+            mastery_level: 40,
+        }
+    }
+
+    fn day_path(directory: &Path, record: &ReviewRecord) -> PathBuf {
+        day_file_path(directory, record.reviewed_at.with_timezone(&Local).date_naive())
+    }
+
+    fn ids(records: &[ReviewRecord]) -> Vec<&str> {
+        records.iter().map(|record| record.id.as_str()).collect()
+    }
+
+    #[test]
+    fn ends_with_newline_checks_last_byte() {
+        let dir = temp_dir("newline");
+        let path = dir.join("file.jsonl");
+
+        for (content, expected) in [("", true), ("{}", false), ("{}\n", true)] {
+            fs::write(&path, content).unwrap();
+            let mut file = File::open(&path).unwrap();
+            assert_eq!(ends_with_newline(&mut file).unwrap(), expected, "{:?}", content);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn append_after_partial_line_starts_new_line() {
+        let dir = temp_dir("partial");
+        let first = record("1", "2025-03-01");
+        let path = day_path(&dir, &first);
+
+        // 上次写入在第二条记录中途中断
+        let partial = serde_json::to_string(&record("2", "2025-03-01")).unwrap();
+        let content = format!("{}\n{}", serde_json::to_string(&first).unwrap(), &partial[..partial.len() / 2]);
+        fs::write(&path, content).unwrap();
+
+        append_records(&dir, &[record("3", "2025-03-01")]).unwrap();
+
+        let records = read_day_file(&path).unwrap();
+        assert_eq!(ids(&records), ["1", "3"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn append_groups_records_by_day() {
+        let dir = temp_dir("days");
+
+        append_records(&dir, &[record("1", "2025-03-01"), record("2", "2025-03-05"), record("3", "2025-03-01")]).unwrap();
+
+        let files = day_files(&dir).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(ids(&read_day_file(&files[0].1).unwrap()), ["1", "3"]);
+        assert_eq!(ids(&read_day_file(&files[1].1).unwrap()), ["2"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn legacy_store_entries_migrate_to_day_files() {
+        let dir = temp_dir("legacy");
+        let entries = vec![
+            ("review_1".to_string(), serde_json::to_value(record("1", "2025-03-01")).unwrap()),
+            ("review_2".to_string(), serde_json::json!({ "id": "2" })),
+            ("daily_stats".to_string(), serde_json::to_value(record("3", "2025-03-01")).unwrap()),
+            ("review_4".to_string(), serde_json::to_value(record("4", "2025-03-02")).unwrap()),
+        ];
+
+        let legacy = legacy_records(entries);
+        let keys: Vec<&str> = legacy.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, ["review_1", "review_4"]);
+
+        let records: Vec<ReviewRecord> = legacy.into_iter().map(|(_, record)| record).collect();
+        append_records(&dir, &records).unwrap();
+
+        let migrated: Vec<ReviewRecord> = day_files(&dir)
+            .unwrap()
+            .into_iter()
+            .flat_map(|(_, path)| read_day_file(&path).unwrap())
+            .collect();
+        assert_eq!(ids(&migrated), ["1", "4"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn restore_keeps_records_appended_after_archive() {
+        let dir = temp_dir("restore");
+        let directory = dir.join("review_log");
+        let archive = dir.join("archive").join("20250301_120000_000");
+
+        append_records(&directory, &[record("1", "2025-03-01"), record("2", "2025-03-02")]).unwrap();
+        assert!(move_to_archive(&directory, &archive).unwrap());
+        assert!(!directory.exists());
+
+        // 归档后同一天和另一天都有新记录
+        append_records(&directory, &[record("3", "2025-03-01"), record("4", "2025-03-03")]).unwrap();
+        merge_archive(&archive, &directory).unwrap();
+
+        assert!(!archive.exists());
+        let days: Vec<Vec<String>> = day_files(&directory)
+            .unwrap()
+            .into_iter()
+            .map(|(_, path)| read_day_file(&path).unwrap().into_iter().map(|record| record.id).collect())
+            .collect();
+        assert_eq!(days, [vec!["1", "3"], vec!["2"], vec!["4"]]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn restore_after_partial_archived_line_keeps_new_records() {
+        let dir = temp_dir("restore-partial");
+        let directory = dir.join("review_log");
+        let archive = dir.join("archive").join("20250301_120000_000");
+
+        let first = record("1", "2025-03-01");
+        fs::create_dir_all(&archive).unwrap();
+        fs::write(day_path(&archive, &first), format!("{}\n{{\"id\":", serde_json::to_string(&first).unwrap())).unwrap();
+        append_records(&directory, &[record("2", "2025-03-01")]).unwrap();
+
+        merge_archive(&archive, &directory).unwrap();
+
+        assert_eq!(ids(&read_day_file(&day_path(&directory, &first)).unwrap()), ["1", "2"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn archive_without_records_restores_to_empty_log() {
+        let dir = temp_dir("empty");
+        let directory = dir.join("review_log");
+        let archive = dir.join("archive").join("20250301_120000_000");
+
+        assert!(!move_to_archive(&directory, &archive).unwrap());
+        assert!(archive.is_dir());
+
+        merge_archive(&archive, &directory).unwrap();
+        assert!(!archive.exists());
+        assert!(day_files(&directory).unwrap().is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
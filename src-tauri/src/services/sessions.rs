@@ -0,0 +1,243 @@
+use crate::models::{DateRange, ReviewRecord, SessionPause, SessionType, StudySession};
+use crate::services::{achievements, review_log};
+use crate::utils::stores;
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use std::sync::Mutex;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+pub const SESSIONS_STORE: &str = "study_sessions.json";
+
+// 正在进行的学习会话，评分时记录到复习记录中
+static ACTIVE: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+/// 旧版本前端保存的会话：结束时保存，只有结束时间和时长
+#[derive(Deserialize)]
+struct LegacySession {
+    session_id: String,
+    timestamp: DateTime<Utc>,
+    #[serde(default)]
+    words_studied: Vec<String>,
+    #[serde(default)]
+    session_duration: u64,
+    #[serde(default)]
+    correct_answers: u32,
+    #[serde(default)]
+    total_answers: u32,
+    #[serde(default)]
+    average_time_per_word: f64,
+    session_type: Option<SessionType>,
+}
+
+impl From<LegacySession> for StudySession {
+    fn from(legacy: LegacySession) -> Self {
+        Self {
+            session_id: legacy.session_id.strip_prefix("session_").unwrap_or(&legacy.session_id).to_string(),
+            session_type: legacy.session_type.unwrap_or(SessionType::Mixed),
+            started_at: legacy.timestamp - chrono::Duration::seconds(legacy.session_duration as i64),
+            ended_at: Some(legacy.timestamp),
+            pauses: Vec::new(),
+            words_studied: legacy.words_studied,
+            session_duration: legacy.session_duration,
+            correct_answers: legacy.correct_answers,
+            total_answers: legacy.total_answers,
+            average_time_per_word: legacy.average_time_per_word,
+        }
+    }
+}
+
+/// 当前会话的 id，没有进行中的会话时返回 None
+pub fn active_id() -> Option<String> {
+    ACTIVE.lock().unwrap().clone()
+}
+
+/// 开始新的学习会话；上次异常退出时没有结束的会话在这里补上结束时间
+pub fn start(app: &AppHandle, session_type: SessionType) -> Result<StudySession> {
+    close_stale(app)?;
+
+    let session = StudySession {
+        session_id: Uuid::new_v4().to_string(),
+        session_type,
+        started_at: Utc::now(),
+        ended_at: None,
+        pauses: Vec::new(),
+        words_studied: Vec::new(),
+        session_duration: 0,
+        correct_answers: 0,
+        total_answers: 0,
+        average_time_per_word: 0.0,
+    };
+    save(app, &session)?;
+    *ACTIVE.lock().unwrap() = Some(session.session_id.clone());
+
+    log::info!("📖 开始学习会话: {} ({:?})", session.session_id, session_type);
+    Ok(session)
+}
+
+/// 暂停学习会话，已暂停时不做处理
+pub fn pause(app: &AppHandle, session_id: &str) -> Result<StudySession> {
+    let mut session = load_active(app, session_id)?;
+    if !session.is_paused() {
+        session.pauses.push(SessionPause {
+            started_at: Utc::now(),
+            ended_at: None,
+        });
+        save(app, &session)?;
+        log::debug!("⏸️ 学习会话已暂停: {}", session_id);
+    }
+    Ok(summarize(app, session))
+}
+
+/// 继续已暂停的学习会话
+pub fn resume(app: &AppHandle, session_id: &str) -> Result<StudySession> {
+    let mut session = load_active(app, session_id)?;
+    if let Some(pause) = session.pauses.last_mut().filter(|pause| pause.ended_at.is_none()) {
+        pause.ended_at = Some(Utc::now());
+        save(app, &session)?;
+        log::debug!("▶️ 学习会话已继续: {}", session_id);
+    }
+    Ok(summarize(app, session))
+}
+
+/// 结束学习会话，根据复习记录汇总答题情况并检查成就
+pub fn end(app: &AppHandle, session_id: &str) -> Result<StudySession> {
+    let session = load_active(app, session_id)?;
+    let session = finish(app, session, Utc::now())?;
+
+    {
+        let mut active = ACTIVE.lock().unwrap();
+        if active.as_deref() == Some(session_id) {
+            *active = None;
+        }
+    }
+
+    log::info!(
+        "✅ 学习会话已结束: {}，{} 秒，答对 {}/{}",
+        session.session_id, session.session_duration, session.correct_answers, session.total_answers
+    );
+    achievements::spawn_evaluate(app, achievements::Trigger::Session {
+        correct: session.correct_answers,
+        total: session.total_answers,
+    });
+    Ok(session)
+}
+
+/// 按 id 读取会话，进行中的会话返回截至现在的汇总
+pub fn get(app: &AppHandle, session_id: &str) -> Result<Option<StudySession>> {
+    Ok(load(app, session_id)?.map(|session| summarize(app, session)))
+}
+
+/// 日期范围内开始的学习会话，最近的在前
+pub fn list(app: &AppHandle, range: DateRange) -> Result<Vec<StudySession>> {
+    let store = stores::open(app, SESSIONS_STORE)?;
+
+    let mut sessions: Vec<StudySession> = store
+        .entries()
+        .into_iter()
+        .filter(|(key, _)| key.starts_with("session_"))
+        .filter_map(|(_, value)| parse(value))
+        .filter(|session| range.contains(session.started_at))
+        .map(|session| summarize(app, session))
+        .collect();
+    sessions.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+
+    Ok(sessions)
+}
+
+fn parse(value: serde_json::Value) -> Option<StudySession> {
+    serde_json::from_value::<StudySession>(value.clone())
+        .ok()
+        .or_else(|| serde_json::from_value::<LegacySession>(value).ok().map(StudySession::from))
+}
+
+fn key(session_id: &str) -> String {
+    format!("session_{}", session_id)
+}
+
+fn load(app: &AppHandle, session_id: &str) -> Result<Option<StudySession>> {
+    let store = stores::open(app, SESSIONS_STORE)?;
+    Ok(store.get(key(session_id)).and_then(parse))
+}
+
+fn load_active(app: &AppHandle, session_id: &str) -> Result<StudySession> {
+    let session = load(app, session_id)?.ok_or_else(|| anyhow!("学习会话不存在: {}", session_id))?;
+    if !session.is_active() {
+        return Err(anyhow!("学习会话已结束: {}", session_id));
+    }
+    Ok(session)
+}
+
+fn save(app: &AppHandle, session: &StudySession) -> Result<()> {
+    let store = stores::open(app, SESSIONS_STORE)?;
+    store.set(key(&session.session_id), serde_json::to_value(session)?);
+    stores::save(app, SESSIONS_STORE)?;
+    Ok(())
+}
+
+// 没有正常结束的会话以最后一次复习（没有复习时为开始时间）作为结束时间
+fn close_stale(app: &AppHandle) -> Result<()> {
+    let active = active_id();
+    let stale: Vec<StudySession> = list(app, DateRange::default())?
+        .into_iter()
+        .filter(|session| session.is_active() && active.as_deref() != Some(session.session_id.as_str()))
+        .collect();
+
+    for session in stale {
+        let records = review_log::load_session(app, &session)?;
+        let ended_at = records.last().map_or(session.started_at, |record| record.reviewed_at);
+        log::info!("📖 补充结束未正常结束的学习会话: {}", session.session_id);
+        finish(app, session, ended_at)?;
+    }
+    Ok(())
+}
+
+fn finish(app: &AppHandle, mut session: StudySession, ended_at: DateTime<Utc>) -> Result<StudySession> {
+    for pause in session.pauses.iter_mut().filter(|pause| pause.ended_at.is_none()) {
+        pause.ended_at = Some(ended_at);
+    }
+    session.ended_at = Some(ended_at);
+
+    let records = review_log::load_session(app, &session)?;
+    aggregate(&mut session, &records, ended_at);
+    save(app, &session)?;
+    Ok(session)
+}
+
+// 进行中的会话按现在的时间汇总；读取失败时保留已保存的数据
+fn summarize(app: &AppHandle, mut session: StudySession) -> StudySession {
+    if session.is_active() {
+        match review_log::load_session(app, &session) {
+            Ok(records) => aggregate(&mut session, &records, Utc::now()),
+            Err(e) => log::warn!("读取学习会话 {} 的复习记录失败: {}", session.session_id, e),
+        }
+    }
+    session
+}
+
+/// 根据会话中的复习记录计算单词列表、答题数和不含暂停的时长
+fn aggregate(session: &mut StudySession, records: &[ReviewRecord], until: DateTime<Utc>) {
+    session.words_studied.clear();
+    for record in records {
+        if !session.words_studied.contains(&record.word_id) {
+            session.words_studied.push(record.word_id.clone());
+        }
+    }
+    session.total_answers = records.len() as u32;
+    session.correct_answers = records.iter().filter(|record| record.is_correct).count() as u32;
+
+    let paused: i64 = session
+        .pauses
+        .iter()
+        .map(|pause| (pause.ended_at.unwrap_or(until) - pause.started_at).num_seconds())
+        .sum();
+    session.session_duration = ((until - session.started_at).num_seconds() - paused).max(0) as u64;
+
+    session.average_time_per_word = if session.words_studied.is_empty() {
+        0.0
+    } else {
+        session.session_duration as f64 / session.words_studied.len() as f64
+    };
+}
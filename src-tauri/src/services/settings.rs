@@ -0,0 +1,405 @@
+use crate::models::{parse_time, Settings, FieldError, PlaybackStep};
+use crate::services::journal;
+use crate::utils::{config, hotkey};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use tauri::{AppHandle, Emitter};
+
+/// 设置中可独立变化的部分，订阅者据此决定是否需要响应
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingsSection {
+    AutoSwitch,
+    SwitchInterval,
+    PausePolicy,
+    AlwaysOnTop,
+    ShowInTray,
+    EnableTts,
+    Tts,
+    ActiveWordbook,
+    WindowSize,
+    Hotkeys,
+    Theme,
+    Learning,
+    Notification,
+}
+
+/// 设置变更事件，同时发送给前端（`settings-changed`）和 Rust 订阅者
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsChange {
+    pub sections: Vec<SettingsSection>,
+    pub old: Settings,
+    pub new: Settings,
+}
+
+impl SettingsChange {
+    pub fn touches(&self, section: SettingsSection) -> bool {
+        self.sections.contains(&section)
+    }
+}
+
+type Subscriber = Arc<dyn Fn(&AppHandle, &SettingsChange) + Send + Sync>;
+
+static SUBSCRIBERS: Lazy<RwLock<Vec<Subscriber>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+const MAX_PLAYBACK_STEPS: usize = 20;
+
+// 设置的读取-合并-校验-写入必须整体串行，否则并发的修改会互相覆盖
+static WRITE_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+static COLOR_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^#([0-9a-fA-F]{3}|[0-9a-fA-F]{6}|[0-9a-fA-F]{8})$").unwrap());
+
+/// 注册设置变更订阅者，设置保存后按注册顺序同步调用
+pub fn subscribe(subscriber: impl Fn(&AppHandle, &SettingsChange) + Send + Sync + 'static) {
+    SUBSCRIBERS.write().unwrap().push(Arc::new(subscriber));
+}
+
+/// 校验并保存完整的设置
+pub async fn update(app: &AppHandle, settings: Settings) -> Result<Settings, Vec<FieldError>> {
+    let _guard = WRITE_LOCK.lock().await;
+    save(app, settings).await
+}
+
+/// 以 JSON Merge Patch (RFC 7386) 语义部分更新设置
+///
+/// 补丁中为 null 的字段恢复为默认值，未出现的字段保持不变。
+pub async fn apply_patch(app: &AppHandle, patch: Value) -> Result<Settings, Vec<FieldError>> {
+    let _guard = WRITE_LOCK.lock().await;
+    let current = config::load_settings().await.map_err(general_error)?;
+
+    let mut merged = serde_json::to_value(&current).map_err(general_error)?;
+    merge_patch(&mut merged, &patch);
+
+    let settings = Settings::from_json(merged).map_err(|e| vec![e])?;
+    save(app, settings).await
+}
+
+// 调用方需持有 WRITE_LOCK
+async fn save(app: &AppHandle, settings: Settings) -> Result<Settings, Vec<FieldError>> {
+    let errors = validate(&settings);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let change = write(app, settings.clone()).await.map_err(general_error)?;
+
+    if let Some(change) = change {
+        journal::record("修改设置", vec![journal::Change::Settings {
+            before: Box::new(change.old),
+            after: Box::new(change.new),
+        }]);
+    }
+
+    Ok(settings)
+}
+
+/// 写入设置并广播变更，不记录操作日志（供撤销/重做使用）
+///
+/// 设置没有实际变化时返回 None。
+pub async fn persist(app: &AppHandle, settings: Settings) -> anyhow::Result<Option<SettingsChange>> {
+    let _guard = WRITE_LOCK.lock().await;
+    write(app, settings).await
+}
+
+async fn write(app: &AppHandle, settings: Settings) -> anyhow::Result<Option<SettingsChange>> {
+    let old = config::load_settings().await?;
+    config::save_settings(&settings).await?;
+
+    Ok(broadcast(app, old, settings))
+}
+
+/// 比较新旧设置并广播变更，设置没有变化时返回 None
+pub fn broadcast(app: &AppHandle, old: Settings, new: Settings) -> Option<SettingsChange> {
+    let sections = diff(&old, &new);
+    if sections.is_empty() {
+        return None;
+    }
+
+    let change = SettingsChange { sections, old, new };
+    notify(app, &change);
+
+    Some(change)
+}
+
+/// 启动时把当前设置中的部分应用到窗口、托盘等，与设置变更走同一套订阅者
+///
+/// 只通知 Rust 订阅者，前端启动时自行读取设置。
+pub fn apply_initial(app: &AppHandle, settings: Settings, sections: Vec<SettingsSection>) {
+    let change = SettingsChange {
+        sections,
+        old: settings.clone(),
+        new: settings,
+    };
+
+    let subscribers: Vec<Subscriber> = SUBSCRIBERS.read().unwrap().clone();
+    for subscriber in subscribers {
+        subscriber(app, &change);
+    }
+}
+
+fn notify(app: &AppHandle, change: &SettingsChange) {
+    log::info!("⚙️ 设置已变更: {:?}", change.sections);
+
+    if let Err(e) = app.emit("settings-changed", change) {
+        log::warn!("发送设置变更事件失败: {}", e);
+    }
+
+    // 复制订阅者列表后再调用，允许订阅者内部读取或注册订阅
+    let subscribers: Vec<Subscriber> = SUBSCRIBERS.read().unwrap().clone();
+    for subscriber in subscribers {
+        subscriber(app, change);
+    }
+}
+
+pub fn diff(old: &Settings, new: &Settings) -> Vec<SettingsSection> {
+    let mut sections = Vec::new();
+
+    let mut check = |changed: bool, section: SettingsSection| {
+        if changed {
+            sections.push(section);
+        }
+    };
+
+    check(old.auto_switch != new.auto_switch, SettingsSection::AutoSwitch);
+    check(old.switch_interval != new.switch_interval, SettingsSection::SwitchInterval);
+    check(old.pause_policy != new.pause_policy, SettingsSection::PausePolicy);
+    check(old.always_on_top != new.always_on_top, SettingsSection::AlwaysOnTop);
+    check(old.show_in_tray != new.show_in_tray, SettingsSection::ShowInTray);
+    check(old.enable_tts != new.enable_tts, SettingsSection::EnableTts);
+    check(old.tts != new.tts, SettingsSection::Tts);
+    check(old.active_wordbook != new.active_wordbook, SettingsSection::ActiveWordbook);
+    check(old.window_size != new.window_size, SettingsSection::WindowSize);
+    check(old.hotkeys != new.hotkeys, SettingsSection::Hotkeys);
+    check(old.theme != new.theme, SettingsSection::Theme);
+    check(old.learning != new.learning, SettingsSection::Learning);
+    check(old.notification != new.notification, SettingsSection::Notification);
+
+    sections
+}
+
+/// 检查设置中各字段的取值范围，返回所有字段错误
+pub fn validate(settings: &Settings) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    check_range(&mut errors, "switch_interval", settings.switch_interval, 1, 3600);
+    check_range(&mut errors, "pause_policy.idle_seconds", settings.pause_policy.idle_seconds, 10, 3600);
+
+    let tts = &settings.tts;
+    if !(0.1..=3.0).contains(&tts.rate) {
+        errors.push(FieldError::new("tts.rate", "语速必须在 0.1-3.0 之间"));
+    }
+    if !(0.1..=2.0).contains(&tts.pitch) {
+        errors.push(FieldError::new("tts.pitch", "音调必须在 0.1-2.0 之间"));
+    }
+    if !(0.0..=1.0).contains(&tts.volume) {
+        errors.push(FieldError::new("tts.volume", "音量必须在 0-1 之间"));
+    }
+
+    if tts.sequence.len() > MAX_PLAYBACK_STEPS {
+        errors.push(FieldError::new("tts.sequence", format!("朗读顺序最多 {} 步", MAX_PLAYBACK_STEPS)));
+    }
+    for (i, step) in tts.sequence.iter().enumerate() {
+        if let PlaybackStep::Pause { millis } = step {
+            check_range(&mut errors, &format!("tts.sequence.{}.millis", i), *millis, 0, 10_000);
+        }
+    }
+
+    check_range(&mut errors, "tts.repeat", tts.repeat, 1, 10);
+    check_range(&mut errors, "tts.repeat_delay", tts.repeat_delay, 0, 10_000);
+
+    if tts.backends.is_empty() {
+        errors.push(FieldError::new("tts.backends", "至少需要一种发音方式"));
+    }
+    let unique: HashSet<_> = tts.backends.iter().collect();
+    if unique.len() != tts.backends.len() {
+        errors.push(FieldError::new("tts.backends", "发音方式不能重复"));
+    }
+    if !tts.command.program.trim().is_empty() && !tts.command.args.iter().any(|arg| arg.contains("{output}")) {
+        errors.push(FieldError::new("tts.command.args", "参数中需要包含 {output} 作为输出文件路径"));
+    }
+
+    let wordbook = &settings.active_wordbook;
+    if wordbook.contains(['/', '\\']) || !wordbook.to_lowercase().ends_with(".xml") {
+        errors.push(FieldError::new("active_wordbook", format!("无效的单词本文件名: {}", wordbook)));
+    }
+
+    check_range(&mut errors, "window_size.width", settings.window_size.width, 200, 3840);
+    check_range(&mut errors, "window_size.height", settings.window_size.height, 200, 2160);
+
+    for issue in hotkey::check_config(&settings.hotkeys) {
+        let message = if issue.suggestions.is_empty() {
+            issue.message
+        } else {
+            format!("{}，可以改用: {}", issue.message, issue.suggestions.join(" / "))
+        };
+        errors.push(FieldError::new(format!("hotkeys.bindings.{}.shortcut", issue.action.id()), message));
+    }
+
+    let theme = &settings.theme;
+    if !COLOR_REGEX.is_match(&theme.accent_color) {
+        errors.push(FieldError::new("theme.accent_color", format!("无效的颜色值: {}", theme.accent_color)));
+    }
+    if !(0.0..=1.0).contains(&theme.transparency) {
+        errors.push(FieldError::new("theme.transparency", "透明度必须在 0-1 之间"));
+    }
+    if theme.font_family.trim().is_empty() {
+        errors.push(FieldError::new("theme.font_family", "字体不能为空"));
+    }
+    check_range(&mut errors, "theme.font_size", theme.font_size, 8, 48);
+
+    check_range(&mut errors, "learning.daily_goal", settings.learning.daily_goal, 1, 1000);
+
+    check_range(&mut errors, "notification.reminder_interval", settings.notification.reminder_interval, 1, 1440);
+    let quiet_hours = &settings.notification.quiet_hours;
+    for (field, value) in [("start", &quiet_hours.start), ("end", &quiet_hours.end)] {
+        if parse_time(value).is_none() {
+            errors.push(FieldError::new(format!("notification.quiet_hours.{}", field), format!("无效的时间: {}，应为 HH:MM", value)));
+        }
+    }
+    if settings.notification.reminder_weekdays.iter().any(|day| *day > 6) {
+        errors.push(FieldError::new("notification.reminder_weekdays", "星期必须在 0-6 之间"));
+    }
+
+    errors
+}
+
+fn check_range(errors: &mut Vec<FieldError>, field: &str, value: u32, min: u32, max: u32) {
+    if value < min || value > max {
+        errors.push(FieldError::new(field, format!("取值必须在 {}-{} 之间，当前为 {}", min, max, value)));
+    }
+}
+
+fn general_error(e: impl std::fmt::Display) -> Vec<FieldError> {
+    vec![FieldError::new("", e.to_string())]
+}
+
+/// RFC 7386 JSON Merge Patch
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch_map) = patch else {
+        *target = patch.clone();
+        return;
+    };
+
+    if !target.is_object() {
+        *target = Value::Object(serde_json::Map::new());
+    }
+
+    if let Value::Object(target_map) = target {
+        for (key, value) in patch_map {
+            if value.is_null() {
+                target_map.remove(key);
+            } else {
+                merge_patch(target_map.entry(key.as_str()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AppAction, HotkeyBinding, HotkeyScope};
+    use serde_json::json;
+
+    fn fields(errors: &[FieldError]) -> Vec<&str> {
+        errors.iter().map(|error| error.field.as_str()).collect()
+    }
+
+    #[test]
+    fn default_settings_are_valid() {
+        assert!(validate(&Settings::default()).is_empty());
+    }
+
+    #[test]
+    fn validate_reports_every_invalid_field() {
+        let mut settings = Settings::default();
+        settings.switch_interval = 0;
+        settings.tts.volume = 1.5;
+        settings.tts.backends.clear();
+        settings.theme.accent_color = "blue".to_string();
+        settings.notification.quiet_hours.start = "25:00".to_string();
+        settings.notification.reminder_weekdays = vec![1, 7];
+
+        let errors = validate(&settings);
+        assert_eq!(
+            fields(&errors),
+            [
+                "switch_interval",
+                "tts.volume",
+                "tts.backends",
+                "theme.accent_color",
+                "notification.quiet_hours.start",
+                "notification.reminder_weekdays",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_rejects_bad_wordbook_names() {
+        for name in ["../other.xml", "dir\\words.xml", "words.json"] {
+            let mut settings = Settings::default();
+            settings.active_wordbook = name.to_string();
+            assert_eq!(fields(&validate(&settings)), ["active_wordbook"], "{}", name);
+        }
+    }
+
+    #[test]
+    fn validate_rejects_unmodified_global_hotkey() {
+        let mut settings = Settings::default();
+        settings.hotkeys.bindings.insert(
+            AppAction::SpeakWord,
+            HotkeyBinding { shortcut: "Space".to_string(), scope: HotkeyScope::Global },
+        );
+
+        let errors = validate(&settings);
+        assert_eq!(fields(&errors), ["hotkeys.bindings.speak_word.shortcut"]);
+    }
+
+    #[test]
+    fn validate_requires_output_placeholder_for_command() {
+        let mut settings = Settings::default();
+        settings.tts.command.program = "piper".to_string();
+        settings.tts.command.args = vec!["--model".to_string(), "en.onnx".to_string()];
+        assert_eq!(fields(&validate(&settings)), ["tts.command.args"]);
+
+        settings.tts.command.args.push("--output_file={output}".to_string());
+        assert!(validate(&settings).is_empty());
+    }
+
+    #[test]
+    fn merge_patch_merges_nested_objects() {
+        let mut target = json!({ "tts": { "rate": 1.0, "pitch": 1.0 }, "auto_switch": true });
+        merge_patch(&mut target, &json!({ "tts": { "rate": 0.5 } }));
+        assert_eq!(target, json!({ "tts": { "rate": 0.5, "pitch": 1.0 }, "auto_switch": true }));
+    }
+
+    #[test]
+    fn merge_patch_null_removes_field() {
+        let mut target = json!({ "tts": { "rate": 1.0, "pitch": 1.0 } });
+        merge_patch(&mut target, &json!({ "tts": { "pitch": null }, "missing": null }));
+        assert_eq!(target, json!({ "tts": { "rate": 1.0 } }));
+    }
+
+    #[test]
+    fn merge_patch_replaces_arrays_and_scalars() {
+        let mut target = json!({ "weekdays": [0, 1, 2], "name": { "nested": true } });
+        merge_patch(&mut target, &json!({ "weekdays": [5], "name": "plain" }));
+        assert_eq!(target, json!({ "weekdays": [5], "name": "plain" }));
+    }
+
+    #[test]
+    fn patched_field_falls_back_to_default() {
+        let mut settings = Settings::default();
+        settings.switch_interval = 30;
+
+        let mut merged = serde_json::to_value(&settings).unwrap();
+        merge_patch(&mut merged, &json!({ "switch_interval": null }));
+
+        let patched = Settings::from_json(merged).unwrap();
+        assert_eq!(patched.switch_interval, Settings::default().switch_interval);
+    }
+}
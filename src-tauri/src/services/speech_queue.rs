@@ -0,0 +1,194 @@
+use crate::models::Word;
+use crate::services::tts;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+static QUEUE: Lazy<Mutex<Queue>> = Lazy::new(|| {
+    Mutex::new(Queue {
+        pending: VecDeque::new(),
+        current: None,
+        running: false,
+        next_id: 1,
+    })
+});
+
+/// 要朗读的内容
+#[derive(Debug, Clone)]
+pub enum SpeechContent {
+    Headword(String), // 只朗读单词（或一段文本）
+    Sequence(Word),   // 按设置中的朗读顺序朗读
+}
+
+impl SpeechContent {
+    fn text(&self) -> &str {
+        match self {
+            SpeechContent::Headword(text) => text,
+            SpeechContent::Sequence(word) => &word.word,
+        }
+    }
+
+    fn word_id(&self) -> Option<&str> {
+        match self {
+            SpeechContent::Headword(_) => None,
+            SpeechContent::Sequence(word) => Some(&word.id),
+        }
+    }
+}
+
+/// `speech-started` / `speech-finished` 事件的内容
+#[derive(Debug, Clone, Serialize)]
+pub struct SpeechEvent {
+    pub id: u64,
+    pub text: String,
+    pub word_id: Option<String>,
+    pub interrupted: bool,     // 被新的朗读或停止操作打断
+    pub error: Option<String>, // 所有发音方式都失败时的原因
+}
+
+struct Job {
+    id: u64,
+    content: SpeechContent,
+    generation: u64, // 入队时的朗读代数，之后被打断的任务不再播放
+}
+
+struct Queue {
+    pending: VecDeque<Job>,
+    current: Option<u64>,
+    running: bool,
+    next_id: u64,
+}
+
+/// 打断正在进行和排队中的朗读，立即朗读新内容，返回任务 id
+pub fn play(app: &AppHandle, content: SpeechContent) -> u64 {
+    let (dropped, id) = {
+        let mut queue = QUEUE.lock().unwrap();
+        let dropped: Vec<Job> = queue.pending.drain(..).collect();
+        let generation = tts::interrupt();
+        (dropped, push(app, &mut queue, content, generation))
+    };
+
+    finish_dropped(app, dropped);
+    id
+}
+
+/// 排在当前朗读之后播放，返回任务 id
+pub fn enqueue(app: &AppHandle, content: SpeechContent) -> u64 {
+    let mut queue = QUEUE.lock().unwrap();
+    push(app, &mut queue, content, tts::generation())
+}
+
+/// 停止朗读并清空队列
+pub fn stop(app: &AppHandle) {
+    let dropped: Vec<Job> = {
+        let mut queue = QUEUE.lock().unwrap();
+        let dropped = queue.pending.drain(..).collect();
+        tts::interrupt();
+        dropped
+    };
+
+    finish_dropped(app, dropped);
+}
+
+/// 是否有正在进行或排队中的朗读
+pub fn is_busy() -> bool {
+    let queue = QUEUE.lock().unwrap();
+    queue.current.is_some() || !queue.pending.is_empty()
+}
+
+// 排队中被丢弃的任务不会开始播放，同样发送被打断的结束事件，前端据此清理等待中的状态
+fn finish_dropped(app: &AppHandle, dropped: Vec<Job>) {
+    if dropped.is_empty() {
+        return;
+    }
+
+    log::debug!("🔇 丢弃 {} 个排队中的朗读", dropped.len());
+    for job in &dropped {
+        emit(app, "speech-finished", job, None);
+    }
+}
+
+fn push(app: &AppHandle, queue: &mut Queue, content: SpeechContent, generation: u64) -> u64 {
+    let id = queue.next_id;
+    queue.next_id += 1;
+    queue.pending.push_back(Job { id, content, generation });
+
+    if !queue.running {
+        queue.running = true;
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move { run(app).await });
+    }
+    id
+}
+
+// 依次播放队列中的任务，队列为空时退出，下次入队时重新启动
+async fn run(app: AppHandle) {
+    loop {
+        let job = {
+            let mut queue = QUEUE.lock().unwrap();
+            match queue.pending.pop_front() {
+                Some(job) => {
+                    queue.current = Some(job.id);
+                    job
+                }
+                None => {
+                    queue.current = None;
+                    queue.running = false;
+                    return;
+                }
+            }
+        };
+
+        play_job(&app, job).await;
+    }
+}
+
+async fn play_job(app: &AppHandle, job: Job) {
+    // 出队前已被打断的任务直接结束
+    if !tts::is_current(job.generation) {
+        emit(app, "speech-finished", &job, None);
+        return;
+    }
+
+    let config = tts::desired_config().await;
+    emit(app, "speech-started", &job, None);
+
+    let mut error = None;
+    for round in 0..config.repeat.max(1) {
+        if round > 0 && !tts::wait(job.generation, Duration::from_millis(config.repeat_delay as u64)).await {
+            break;
+        }
+
+        let result = match &job.content {
+            SpeechContent::Headword(text) => tts::speak_headword(text, job.generation, &config).await,
+            SpeechContent::Sequence(word) => tts::speak_sequence(word, job.generation, &config).await,
+        };
+        match result {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => {
+                log::warn!("朗读失败 {}: {}", job.content.text(), e);
+                error = Some(e.to_string());
+                break;
+            }
+        }
+    }
+
+    emit(app, "speech-finished", &job, error);
+}
+
+fn emit(app: &AppHandle, event: &str, job: &Job, error: Option<String>) {
+    let payload = SpeechEvent {
+        id: job.id,
+        text: job.content.text().to_string(),
+        word_id: job.content.word_id().map(str::to_string),
+        interrupted: !tts::is_current(job.generation),
+        error,
+    };
+    if let Err(e) = app.emit(event, &payload) {
+        log::warn!("发送朗读事件失败: {}", e);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::models::{AppAction, Word};
+use crate::services::settings::{self, SettingsChange, SettingsSection};
+use crate::services::{actions, current_word, progress, reminders};
+use crate::services::window::MAIN_WINDOW;
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Emitter, Manager, Wry};
+
+pub const TRAY_ID: &str = "main";
+
+const APP_NAME: &str = "小驴单词薄";
+// 菜单标题中释义的最大字符数，过长的释义会把菜单撑得很宽
+const MAX_TRANS_CHARS: usize = 24;
+
+// 托盘快速评分对应的掌握度，与前端「认识 / 不认识」一致
+const KNOWN_MASTERY: u8 = 95;
+const UNKNOWN_MASTERY: u8 = 10;
+
+/// 订阅设置变更，立即应用托盘图标的显示状态
+pub fn register_settings_subscriber() {
+    settings::subscribe(on_settings_changed);
+}
+
+fn on_settings_changed(app: &AppHandle, change: &SettingsChange) {
+    if !change.touches(SettingsSection::ShowInTray) {
+        return;
+    }
+
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        if let Err(e) = tray.set_visible(change.new.show_in_tray) {
+            log::warn!("切换托盘图标显示状态失败: {}", e);
+        }
+    }
+}
+
+/// 创建托盘图标，菜单内容随当前单词和今日进度更新
+pub fn build(app: &AppHandle) -> tauri::Result<()> {
+    let menu = build_menu(app, current_word::get().as_ref())?;
+
+    TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&menu)
+        .tooltip(APP_NAME)
+        .icon(app.default_window_icon().unwrap().clone())
+        .on_menu_event(|app, event| on_menu_event(app, event.id.as_ref()))
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_window(tray.app_handle());
+            }
+        })
+        .build(app)?;
+
+    Ok(())
+}
+
+/// 按当前单词和今日进度重建托盘提示和菜单
+pub fn refresh(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+
+    let word = current_word::get();
+    let tooltip = match &word {
+        Some(word) => format!("{}\n{} - {}", APP_NAME, word.word, word.trans),
+        None => APP_NAME.to_string(),
+    };
+    if let Err(e) = tray.set_tooltip(Some(tooltip)) {
+        log::warn!("更新托盘提示失败: {}", e);
+    }
+
+    match build_menu(app, word.as_ref()) {
+        Ok(menu) => {
+            if let Err(e) = tray.set_menu(Some(menu)) {
+                log::warn!("更新托盘菜单失败: {}", e);
+            }
+        }
+        Err(e) => log::warn!("创建托盘菜单失败: {}", e),
+    }
+}
+
+fn build_menu(app: &AppHandle, word: Option<&Word>) -> tauri::Result<Menu<Wry>> {
+    let header_text = match word {
+        Some(word) => format!("{}  {}", word.word, truncate(&word.trans, MAX_TRANS_CHARS)),
+        None => "暂无单词".to_string(),
+    };
+    let header = MenuItem::with_id(app, "tray_word", header_text, false, None::<&str>)?;
+
+    let progress_text = match progress::today(app) {
+        Ok(today) => format!("今日进度: {}/{}", today.studied, today.goal),
+        Err(e) => {
+            log::warn!("读取今日进度失败: {}", e);
+            "今日进度: -".to_string()
+        }
+    };
+    let daily = MenuItem::with_id(app, "tray_progress", progress_text, false, None::<&str>)?;
+
+    let has_word = word.is_some();
+    let next = actions::menu_item(app, AppAction::NextWord)?;
+    let prev = actions::menu_item(app, AppAction::PrevWord)?;
+    let speak = actions::menu_item(app, AppAction::SpeakWord)?;
+    let known = MenuItem::with_id(app, "tray_known", "认识", has_word, None::<&str>)?;
+    let unknown = MenuItem::with_id(app, "tray_unknown", "不认识", has_word, None::<&str>)?;
+
+    // 发送学习提醒后在菜单顶部显示「开始学习」，桌面通知本身无法点击打开窗口
+    let study_now = reminders::is_pending()
+        .then(|| actions::menu_item(app, AppAction::StudyNow))
+        .transpose()?;
+
+    let show = MenuItem::with_id(app, "tray_show", "显示", true, None::<&str>)?;
+    let hide = MenuItem::with_id(app, "tray_hide", "隐藏", true, None::<&str>)?;
+    let settings = MenuItem::with_id(app, "tray_settings", "设置", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "tray_quit", "退出", true, None::<&str>)?;
+    let separator = PredefinedMenuItem::separator(app)?;
+
+    let menu = Menu::with_items(app, &[
+        &header, &daily, &separator,
+        &next, &prev, &speak, &known, &unknown, &separator,
+        &show, &hide, &separator,
+        &settings, &separator,
+        &quit,
+    ])?;
+    if let Some(study_now) = study_now {
+        menu.insert(&study_now, 2)?;
+    }
+    Ok(menu)
+}
+
+// 操作类菜单项（下一个、上一个、朗读）由应用菜单事件统一分发，这里只处理托盘专属的菜单项
+fn on_menu_event(app: &AppHandle, id: &str) {
+    match id {
+        "tray_quit" => app.exit(0),
+        "tray_show" => show_window(app),
+        "tray_hide" => {
+            if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+                let _ = window.hide();
+            }
+        }
+        "tray_settings" => {
+            show_window(app);
+            if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+                // 发送事件到前端打开设置面板
+                let _ = window.emit("open-settings", ());
+            }
+        }
+        "tray_known" => grade_current_word(app, true),
+        "tray_unknown" => grade_current_word(app, false),
+        _ => {}
+    }
+}
+
+/// 托盘快速评分：记录当前单词的复习结果，认识的单词直接切换到下一个
+fn grade_current_word(app: &AppHandle, known: bool) {
+    let Some(word) = current_word::get() else {
+        return;
+    };
+
+    let (mastery_level, is_correct) = if known {
+        (KNOWN_MASTERY, true)
+    } else {
+        (UNKNOWN_MASTERY, false)
+    };
+    // 与前端一致：每 20 点掌握度对应一级进度
+    let level = ((mastery_level + 19) / 20).min(5);
+
+    if let Err(e) = progress::record_review(app, &word.id, level, mastery_level, is_correct) {
+        log::warn!("托盘评分失败 {}: {}", word.word, e);
+        return;
+    }
+
+    if known {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = current_word::next(&app).await {
+                log::warn!("切换到下一个单词失败: {}", e);
+            }
+        });
+    }
+}
+
+fn show_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max_chars).collect();
+    format!("{}…", truncated)
+}
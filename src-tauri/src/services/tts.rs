@@ -1,21 +1,264 @@
+use crate::models::{Accent, PlaybackStep, PronunciationBackendKind, TtsConfig, Word};
+use crate::services::pronunciation::{self, Language, PronunciationBackend, SpeechRequest};
+use crate::services::settings::{self, SettingsChange, SettingsSection};
+use crate::utils::config;
 use anyhow::Result;
-use std::sync::Arc;
-use tts::Tts;
-use tokio::sync::Mutex;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tts::{Tts, Voice};
 
 lazy_static::lazy_static! {
-    static ref TTS_INSTANCE: Arc<Mutex<Option<Tts>>> = Arc::new(Mutex::new(None));
+    static ref TTS_INSTANCE: Arc<Mutex<Option<Engine>>> = Arc::new(Mutex::new(None));
 }
 
-pub async fn play_pronunciation(word: &str, phonetic: Option<&str>) -> Result<()> {
-    let mut tts_guard = TTS_INSTANCE.lock().await;
-    
-    // 初始化TTS引擎（如果还未初始化）
+// 设置中的语音配置，首次使用时从设置文件读取，之后由设置订阅更新
+static CONFIG: Lazy<Mutex<Option<TtsConfig>>> = Lazy::new(|| Mutex::new(None));
+
+// 朗读代数，打断朗读时递增，正在播放的朗读据此得知已被取消
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+// 刚调用 speak 时引擎可能还没开始朗读，这段时间内不根据 is_speaking 判断结束
+const START_GRACE: Duration = Duration::from_millis(300);
+
+const PREVIEW_TEXT: &str = "Hello, this is a preview of the selected voice.";
+
+struct Engine {
+    tts: Tts,
+    applied: Option<TtsConfig>, // 已应用到引擎的配置，None 表示需要重新应用
+}
+
+/// 系统中可用的语音，供设置界面选择
+#[derive(Debug, Clone, Serialize)]
+pub struct VoiceInfo {
+    pub id: String,
+    pub name: String,
+    pub language: String,
+}
+
+impl From<&Voice> for VoiceInfo {
+    fn from(voice: &Voice) -> Self {
+        Self {
+            id: voice.id(),
+            name: voice.name(),
+            language: voice.language().to_string(),
+        }
+    }
+}
+
+/// 订阅设置变更，语音配置在下一次朗读前应用到引擎
+pub fn register_settings_subscriber() {
+    settings::subscribe(on_settings_changed);
+}
+
+fn on_settings_changed(_app: &AppHandle, change: &SettingsChange) {
+    if change.touches(SettingsSection::Tts) {
+        *CONFIG.lock().unwrap() = Some(change.new.tts.clone());
+    }
+}
+
+/// 朗读单词本身（不朗读音标）并等待结束，返回是否完整播放，被打断时为 false
+pub async fn speak_headword(word: &str, generation: u64, config: &TtsConfig) -> Result<bool> {
+    let Some(kind) = pronunciation::speak(SpeechRequest::headword(word, generation), config.clone()).await? else {
+        return Ok(false);
+    };
+    log::debug!("开始播放单词发音 ({:?}): {}", kind, word);
+    Ok(wait_until_done(generation, word).await)
+}
+
+/// 按设置中的朗读顺序朗读单词（单词、停顿、例句、释义……）并等待结束
+///
+/// 朗读代数变化时中断并返回 false，缺少对应内容的步骤（如没有例句）直接跳过。
+pub async fn speak_sequence(word: &Word, generation: u64, config: &TtsConfig) -> Result<bool> {
+    for step in &config.sequence {
+        if !is_current(generation) {
+            log::debug!("朗读已取消: {}", word.word);
+            return Ok(false);
+        }
+
+        if let PlaybackStep::Pause { millis } = step {
+            wait(generation, Duration::from_millis(*millis as u64)).await;
+            continue;
+        }
+
+        let Some(request) = segment(word, step, generation) else {
+            continue;
+        };
+
+        let text = request.text.clone();
+        match pronunciation::speak(request, config.clone()).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return Ok(false),
+            Err(e) => {
+                log::warn!("朗读片段失败 {:?}: {}", step, e);
+                continue;
+            }
+        }
+        wait_until_done(generation, &text).await;
+    }
+
+    Ok(is_current(generation))
+}
+
+// 朗读步骤对应的朗读内容
+fn segment(word: &Word, step: &PlaybackStep, generation: u64) -> Option<SpeechRequest> {
+    let example = |index: &u32| word.examples.get(*index as usize);
+
+    let (text, language, voice_id) = match step {
+        PlaybackStep::Word { voice_id } => (word.word.clone(), Language::English, voice_id),
+        PlaybackStep::Example { index, voice_id } => (example(index)?.source.clone(), Language::English, voice_id),
+        PlaybackStep::Translation { voice_id } => (word.trans.clone(), Language::Chinese, voice_id),
+        PlaybackStep::ExampleTranslation { index, voice_id } => (example(index)?.trans.clone(), Language::Chinese, voice_id),
+        PlaybackStep::Pause { .. } => return None,
+    };
+
+    if text.trim().is_empty() {
+        return None;
+    }
+    Some(SpeechRequest {
+        text,
+        language,
+        voice_id: voice_id.clone(),
+        headword: matches!(step, PlaybackStep::Word { .. }),
+        generation,
+    })
+}
+
+/// 系统语音，作为发音方式之一由 `services::pronunciation` 调度
+pub struct SystemTtsBackend;
+
+impl PronunciationBackend for SystemTtsBackend {
+    fn kind(&self) -> PronunciationBackendKind {
+        PronunciationBackendKind::SystemTts
+    }
+
+    fn supports(&self, _request: &SpeechRequest, _config: &TtsConfig) -> bool {
+        true
+    }
+
+    fn speak(&self, request: &SpeechRequest, config: &TtsConfig) -> Result<()> {
+        let mut tts_guard = TTS_INSTANCE.lock().unwrap();
+        let engine = prepare(&mut tts_guard, config)?;
+
+        // 英文默认使用设置中的语音（prepare 已应用），其余情况临时切换语音
+        let voices = engine.tts.voices()?;
+        let voice = if !request.voice_id.is_empty() {
+            voices.iter().find(|voice| voice.id() == request.voice_id)
+        } else if request.language == Language::Chinese {
+            let chinese = voices
+                .iter()
+                .find(|voice| voice.language().to_string().to_lowercase().starts_with("zh"));
+            if chinese.is_none() {
+                return Err(anyhow::anyhow!("没有可用的中文语音"));
+            }
+            chinese
+        } else {
+            None
+        };
+
+        if let Some(voice) = voice {
+            engine.tts.set_voice(voice)?;
+            // 下一次使用默认语音的朗读会重新应用设置
+            engine.applied = None;
+        }
+
+        // 由播放队列保证同一时间只有一段朗读，这里直接打断引擎中残留的朗读
+        engine.tts.speak(&request.text, true)?;
+        Ok(())
+    }
+
+    fn stop(&self) {
+        if let Some(engine) = TTS_INSTANCE.lock().unwrap().as_mut() {
+            if let Err(e) = engine.tts.stop() {
+                log::debug!("停止TTS播放失败（可能不支持）: {}", e);
+            }
+        }
+    }
+
+    fn is_speaking(&self) -> Result<bool> {
+        match TTS_INSTANCE.lock().unwrap().as_ref() {
+            Some(engine) => Ok(engine.tts.is_speaking()?),
+            None => Ok(false),
+        }
+    }
+}
+
+/// 取消正在进行的朗读并停止所有发音方式的播放，返回新的朗读代数
+pub fn interrupt() -> u64 {
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    pronunciation::stop();
+    generation
+}
+
+pub fn generation() -> u64 {
+    GENERATION.load(Ordering::SeqCst)
+}
+
+pub fn is_current(generation: u64) -> bool {
+    GENERATION.load(Ordering::SeqCst) == generation
+}
+
+/// 等待指定时长，期间被取消时提前返回 false
+pub async fn wait(generation: u64, duration: Duration) -> bool {
+    let started = Instant::now();
+    while started.elapsed() < duration {
+        if !is_current(generation) {
+            return false;
+        }
+        tokio::time::sleep(POLL_INTERVAL.min(duration - started.elapsed())).await;
+    }
+    is_current(generation)
+}
+
+/// 等待当前片段朗读结束；部分平台不支持查询朗读状态，此时按文本长度估算时长
+async fn wait_until_done(generation: u64, text: &str) -> bool {
+    let estimated = Duration::from_millis(400 + 90 * text.chars().count() as u64);
+    let limit = estimated * 3;
+    let started = Instant::now();
+
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        if !is_current(generation) {
+            return false;
+        }
+
+        let elapsed = started.elapsed();
+        match is_speaking().await {
+            Ok(true) if elapsed < limit => continue,
+            Ok(false) if elapsed < START_GRACE => continue,
+            Err(_) if elapsed < estimated => continue,
+            _ => return true,
+        }
+    }
+}
+
+pub async fn desired_config() -> TtsConfig {
+    if let Some(config) = CONFIG.lock().unwrap().clone() {
+        return config;
+    }
+
+    let config = match config::load_settings().await {
+        Ok(settings) => settings.tts,
+        Err(e) => {
+            log::warn!("读取语音设置失败，使用默认设置: {}", e);
+            TtsConfig::default()
+        }
+    };
+    *CONFIG.lock().unwrap() = Some(config.clone());
+    config
+}
+
+/// 初始化TTS引擎（如果还未初始化），配置有变化时才重新应用
+fn prepare<'a>(tts_guard: &'a mut MutexGuard<'_, Option<Engine>>, config: &TtsConfig) -> Result<&'a mut Engine> {
     if tts_guard.is_none() {
         match Tts::default() {
             Ok(tts) => {
                 log::info!("TTS引擎初始化成功");
-                *tts_guard = Some(tts);
+                **tts_guard = Some(Engine { tts, applied: None });
             }
             Err(e) => {
                 log::error!("TTS引擎初始化失败: {}", e);
@@ -23,129 +266,126 @@ pub async fn play_pronunciation(word: &str, phonetic: Option<&str>) -> Result<()
             }
         }
     }
-    
-    if let Some(ref mut tts) = *tts_guard {
-        // 设置语音参数
-        if let Err(e) = configure_tts(tts).await {
+
+    let engine = tts_guard.as_mut().unwrap();
+    if engine.applied.as_ref() != Some(config) {
+        if let Err(e) = configure_tts(&mut engine.tts, config) {
             log::warn!("TTS配置失败: {}", e);
         }
-        
-        // 播放单词发音
-        let text_to_speak = if let Some(_phonetic) = phonetic {
-            format!("{}", word) // 只播放单词，不播放音标
-        } else {
-            word.to_string()
-        };
-        
-        match tts.speak(&text_to_speak, false) {
-            Ok(_) => {
-                log::debug!("开始播放单词发音: {}", word);
-                Ok(())
-            }
-            Err(e) => {
-                log::error!("播放单词发音失败: {}", e);
-                Err(anyhow::anyhow!("播放单词发音失败: {}", e))
-            }
-        }
-    } else {
-        Err(anyhow::anyhow!("TTS引擎未初始化"))
+        engine.applied = Some(config.clone());
     }
+
+    Ok(engine)
 }
 
-async fn configure_tts(tts: &mut Tts) -> Result<()> {
-    // 获取可用的语音
+fn configure_tts(tts: &mut Tts, config: &TtsConfig) -> Result<()> {
     let voices = tts.voices()?;
-    
-    // 寻找英语语音
-    let english_voice = voices.iter().find(|voice| {
-        voice.language().to_lowercase().contains("en") ||
-        voice.name().to_lowercase().contains("english") ||
-        voice.name().to_lowercase().contains("david") ||
-        voice.name().to_lowercase().contains("zira") ||
-        voice.name().to_lowercase().contains("mark")
-    });
-    
-    if let Some(voice) = english_voice {
+
+    if let Some(voice) = select_voice(&voices, config) {
         if let Err(e) = tts.set_voice(voice) {
             log::warn!("设置TTS语音失败: {}", e);
         } else {
-            log::info!("设置TTS语音: {}", voice.name());
+            log::info!("设置TTS语音: {} ({})", voice.name(), voice.language());
         }
     }
-    
-    // 尝试设置语速
-    if let Err(e) = tts.set_rate(0.8) { // 稍慢一点的语速
+
+    // 语速和音调按语音默认值的倍数设置，音量按引擎支持的范围换算
+    let rate = (tts.normal_rate() * config.rate).clamp(tts.min_rate(), tts.max_rate());
+    if let Err(e) = tts.set_rate(rate) {
         log::debug!("设置TTS语速失败（可能不支持）: {}", e);
     }
-    
-    // 尝试设置音量
-    if let Err(e) = tts.set_volume(0.8) {
+
+    let volume = tts.min_volume() + (tts.max_volume() - tts.min_volume()) * config.volume;
+    if let Err(e) = tts.set_volume(volume) {
         log::debug!("设置TTS音量失败（可能不支持）: {}", e);
     }
-    
-    // 尝试设置音调
-    if let Err(e) = tts.set_pitch(1.0) {
+
+    let pitch = (tts.normal_pitch() * config.pitch).clamp(tts.min_pitch(), tts.max_pitch());
+    if let Err(e) = tts.set_pitch(pitch) {
         log::debug!("设置TTS音调失败（可能不支持）: {}", e);
     }
-    
+
     Ok(())
 }
 
-pub async fn stop_speaking() -> Result<()> {
-    let mut tts_guard = TTS_INSTANCE.lock().await;
-    
-    if let Some(ref mut tts) = *tts_guard {
-        if let Err(e) = tts.stop() {
-            log::debug!("停止TTS播放失败（可能不支持）: {}", e);
+/// 优先使用设置中指定的语音，找不到时按口音选择英语语音
+fn select_voice<'a>(voices: &'a [Voice], config: &TtsConfig) -> Option<&'a Voice> {
+    if !config.voice_id.is_empty() {
+        match voices.iter().find(|voice| voice.id() == config.voice_id) {
+            Some(voice) => return Some(voice),
+            None => log::warn!("未找到设置中的语音 {}，按口音自动选择", config.voice_id),
         }
     }
-    
-    Ok(())
+
+    let region = match config.accent {
+        Accent::Us => "us",
+        Accent::Uk => "gb",
+    };
+    let language = |voice: &Voice| voice.language().to_string().to_lowercase().replace('_', "-");
+
+    voices
+        .iter()
+        .find(|voice| language(voice) == format!("en-{}", region))
+        .or_else(|| voices.iter().find(|voice| language(voice).starts_with("en")))
+        .or_else(|| {
+            voices.iter().find(|voice| {
+                let name = voice.name().to_lowercase();
+                ["english", "david", "zira", "mark"].iter().any(|n| name.contains(n))
+            })
+        })
 }
 
 pub async fn is_speaking() -> Result<bool> {
-    let tts_guard = TTS_INSTANCE.lock().await;
-    
-    if let Some(ref tts) = *tts_guard {
-        Ok(tts.is_speaking()?)
-    } else {
-        Ok(false)
-    }
+    pronunciation::is_speaking()
 }
 
-pub async fn get_available_voices() -> Result<Vec<String>> {
-    let tts = Tts::default()?;
-    let voices = tts.voices()?;
-    
-    let voice_names: Vec<String> = voices
+/// 各发音方式的状态和最近一次失败的原因
+pub async fn backend_status() -> Vec<pronunciation::BackendStatus> {
+    pronunciation::status(&desired_config().await)
+}
+
+pub async fn get_available_voices() -> Result<Vec<VoiceInfo>> {
+    let config = desired_config().await;
+    let mut tts_guard = TTS_INSTANCE.lock().unwrap();
+    let engine = prepare(&mut tts_guard, &config)?;
+
+    let voices = engine.tts.voices()?;
+    Ok(voices.iter().map(VoiceInfo::from).collect())
+}
+
+/// 用指定语音朗读一段试听文本，不修改设置；下一次朗读时恢复设置中的语音
+pub async fn preview_voice(voice_id: &str, text: Option<&str>) -> Result<()> {
+    let config = desired_config().await;
+    let mut tts_guard = TTS_INSTANCE.lock().unwrap();
+    let engine = prepare(&mut tts_guard, &config)?;
+
+    let voices = engine.tts.voices()?;
+    let voice = voices
         .iter()
-        .map(|voice| format!("{} ({})", voice.name(), voice.language()))
-        .collect();
-    
-    Ok(voice_names)
+        .find(|voice| voice.id() == voice_id)
+        .ok_or_else(|| anyhow::anyhow!("未找到指定的语音: {}", voice_id))?;
+
+    engine.tts.set_voice(voice)?;
+    engine.applied = None;
+    engine.tts.speak(text.unwrap_or(PREVIEW_TEXT), true)?;
+
+    log::debug!("试听TTS语音: {}", voice.name());
+    Ok(())
 }
 
-pub async fn set_voice_by_name(voice_name: &str) -> Result<()> {
-    let mut tts_guard = TTS_INSTANCE.lock().await;
-    
-    if tts_guard.is_none() {
-        *tts_guard = Some(Tts::default()?);
-    }
-    
-    if let Some(ref mut tts) = *tts_guard {
-        let voices = tts.voices()?;
-        let target_voice = voices.iter().find(|voice| {
-            voice.name().to_lowercase().contains(&voice_name.to_lowercase())
-        });
-        
-        if let Some(voice) = target_voice {
-            tts.set_voice(voice)?;
-            log::info!("切换到TTS语音: {}", voice.name());
-        } else {
-            return Err(anyhow::anyhow!("未找到指定的语音: {}", voice_name));
-        }
-    }
-    
+/// 按名称选择语音并写入设置
+pub async fn set_voice_by_name(app: &AppHandle, voice_name: &str) -> Result<()> {
+    let voices = get_available_voices().await?;
+    let target_voice = voices
+        .iter()
+        .find(|voice| voice.name.to_lowercase().contains(&voice_name.to_lowercase()))
+        .ok_or_else(|| anyhow::anyhow!("未找到指定的语音: {}", voice_name))?;
+
+    settings::apply_patch(app, serde_json::json!({ "tts": { "voice_id": target_voice.id } }))
+        .await
+        .map_err(|errors| anyhow::anyhow!("{:?}", errors))?;
+
+    log::info!("切换到TTS语音: {}", target_voice.name);
     Ok(())
 }
 
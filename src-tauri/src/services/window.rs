@@ -0,0 +1,28 @@
+use crate::services::settings::{self, SettingsChange, SettingsSection};
+use tauri::{AppHandle, LogicalSize, Manager};
+
+pub const MAIN_WINDOW: &str = "main";
+
+/// 订阅设置变更，立即应用置顶与窗口尺寸
+pub fn register_settings_subscriber() {
+    settings::subscribe(on_settings_changed);
+}
+
+fn on_settings_changed(app: &AppHandle, change: &SettingsChange) {
+    let Some(window) = app.get_webview_window(MAIN_WINDOW) else {
+        return;
+    };
+
+    if change.touches(SettingsSection::AlwaysOnTop) {
+        if let Err(e) = window.set_always_on_top(change.new.always_on_top) {
+            log::warn!("设置窗口置顶失败: {}", e);
+        }
+    }
+
+    if change.touches(SettingsSection::WindowSize) {
+        let size = &change.new.window_size;
+        if let Err(e) = window.set_size(LogicalSize::new(size.width, size.height)) {
+            log::warn!("调整窗口尺寸失败: {}", e);
+        }
+    }
+}
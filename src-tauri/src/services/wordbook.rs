@@ -0,0 +1,359 @@
+use crate::models::{Word, WordInput, Example};
+use crate::services::settings::{self, SettingsChange, SettingsSection};
+use crate::services::{current_word, progress, xml_parser};
+use crate::utils::config;
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use tauri::{AppHandle, Emitter};
+
+const MAX_WORD_LENGTH: usize = 100;
+
+pub async fn create_word(input: WordInput) -> Result<Word> {
+    let input = normalize_input(input)?;
+
+    xml_parser::modify_words(|words| {
+        ensure_unique_headword(words, &input.word, None)?;
+
+        let mut word = Word::new(input.word, input.trans, input.phonetic);
+        word.tags = input.tags;
+        word.examples = input.examples;
+        word.difficulty = input.difficulty.unwrap_or(word.difficulty);
+        word.note = input.note;
+
+        words.push(word.clone());
+        log::info!("✅ 已新增单词: {}", word.word);
+        Ok(word)
+    })
+    .await
+}
+
+pub async fn update_word(id: &str, input: WordInput) -> Result<Word> {
+    let input = normalize_input(input)?;
+
+    xml_parser::modify_words(|words| {
+        ensure_unique_headword(words, &input.word, Some(id))?;
+
+        let word = find_word_mut(words, id)?;
+        word.word = input.word;
+        word.trans = input.trans;
+        word.phonetic = input.phonetic;
+        word.tags = input.tags;
+        word.examples = input.examples;
+        if let Some(difficulty) = input.difficulty {
+            word.difficulty = difficulty;
+        }
+        word.note = input.note;
+        word.updated_at = Utc::now();
+
+        log::info!("✅ 已更新单词: {}", word.word);
+        Ok(word.clone())
+    })
+    .await
+}
+
+pub async fn delete_word(id: &str) -> Result<Word> {
+    xml_parser::modify_words(|words| {
+        let index = words
+            .iter()
+            .position(|w| w.id == id)
+            .ok_or_else(|| anyhow!("未找到单词: {}", id))?;
+
+        let removed = words.remove(index);
+        log::info!("🗑️ 已删除单词: {}", removed.word);
+        Ok(removed)
+    })
+    .await
+}
+
+pub async fn add_example(word_id: &str, example: Example) -> Result<Word> {
+    let example = normalize_example(example)?;
+
+    xml_parser::modify_words(|words| {
+        let word = find_word_mut(words, word_id)?;
+        word.examples.push(example);
+        word.updated_at = Utc::now();
+        Ok(word.clone())
+    })
+    .await
+}
+
+pub async fn update_example(word_id: &str, index: usize, example: Example) -> Result<Word> {
+    let example = normalize_example(example)?;
+
+    xml_parser::modify_words(|words| {
+        let word = find_word_mut(words, word_id)?;
+        let slot = word
+            .examples
+            .get_mut(index)
+            .ok_or_else(|| anyhow!("例句索引超出范围: {}", index))?;
+        *slot = example;
+        word.updated_at = Utc::now();
+        Ok(word.clone())
+    })
+    .await
+}
+
+pub async fn delete_example(word_id: &str, index: usize) -> Result<Word> {
+    xml_parser::modify_words(|words| {
+        let word = find_word_mut(words, word_id)?;
+        if index >= word.examples.len() {
+            return Err(anyhow!("例句索引超出范围: {}", index));
+        }
+        word.examples.remove(index);
+        word.updated_at = Utc::now();
+        Ok(word.clone())
+    })
+    .await
+}
+
+/// 为多个单词批量添加标签，返回实际发生变化的单词数量
+pub async fn add_tags(word_ids: &[String], tags: &[String]) -> Result<u32> {
+    let tags = normalize_tags(tags.to_vec())?;
+
+    xml_parser::modify_words(|words| {
+        let mut changed = 0;
+        for word in words.iter_mut().filter(|w| word_ids.contains(&w.id)) {
+            let before = word.tags.len();
+            for tag in &tags {
+                if !word.tags.contains(tag) {
+                    word.tags.push(tag.clone());
+                }
+            }
+            if word.tags.len() != before {
+                word.updated_at = Utc::now();
+                changed += 1;
+            }
+        }
+        Ok(changed)
+    })
+    .await
+}
+
+/// 从多个单词中批量移除标签，返回实际发生变化的单词数量
+pub async fn remove_tags(word_ids: &[String], tags: &[String]) -> Result<u32> {
+    let tags = normalize_tags(tags.to_vec())?;
+
+    xml_parser::modify_words(|words| {
+        let mut changed = 0;
+        for word in words.iter_mut().filter(|w| word_ids.contains(&w.id)) {
+            let before = word.tags.len();
+            word.tags.retain(|tag| !tags.contains(tag));
+            if word.tags.len() != before {
+                word.updated_at = Utc::now();
+                changed += 1;
+            }
+        }
+        Ok(changed)
+    })
+    .await
+}
+
+/// 当前单词本的统计信息
+#[derive(Debug, Clone, Serialize)]
+pub struct WordbookStats {
+    pub name: String,
+    pub total_words: u32,
+    pub tag_count: u32,
+    pub learned_words: u32,
+    pub mastered_words: u32,
+}
+
+/// 按设置选择单词本，并在设置中的单词本变化时切换
+pub async fn init(app: &AppHandle) -> Result<()> {
+    let current = config::load_settings().await?;
+    let name = if wordbook_exists(&current.active_wordbook)? {
+        current.active_wordbook
+    } else {
+        log::warn!("单词本 {} 不存在，使用默认单词本", current.active_wordbook);
+        xml_parser::DEFAULT_WORDBOOK.to_string()
+    };
+    xml_parser::set_active_wordbook(&name).await;
+
+    settings::subscribe(on_settings_changed);
+    Ok(())
+}
+
+/// 列出数据目录 vocabulary 下的所有单词本，默认单词本总是在列表中
+pub fn list_wordbooks() -> Result<Vec<String>> {
+    let mut names = vec![xml_parser::DEFAULT_WORDBOOK.to_string()];
+
+    let directory = xml_parser::get_vocabulary_directory()?;
+    if directory.exists() {
+        for entry in fs::read_dir(&directory)? {
+            let path = entry?.path();
+            let is_xml = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("xml"));
+            if let (true, Some(name)) = (is_xml, path.file_name().and_then(|n| n.to_str())) {
+                if !names.iter().any(|n| n == name) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+    }
+
+    names[1..].sort();
+    Ok(names)
+}
+
+/// 切换到指定单词本，写入设置后由设置订阅完成加载
+pub async fn switch_wordbook(app: &AppHandle, name: &str) -> Result<()> {
+    if !wordbook_exists(name)? {
+        return Err(anyhow!("单词本不存在: {}", name));
+    }
+
+    settings::apply_patch(app, serde_json::json!({ "active_wordbook": name }))
+        .await
+        .map_err(|errors| anyhow!("{:?}", errors))?;
+    Ok(())
+}
+
+pub async fn stats(app: &AppHandle) -> Result<WordbookStats> {
+    let words = xml_parser::load_words(None).await?;
+    let ids: HashSet<&str> = words.iter().map(|w| w.id.as_str()).collect();
+    let tags: HashSet<&str> = words.iter().flat_map(|w| w.tags.iter().map(|t| t.as_str())).collect();
+
+    // 学习进度按单词 ID 保存，只统计属于当前单词本的部分
+    let progresses: Vec<_> = progress::load_all(app)?
+        .into_iter()
+        .filter(|p| ids.contains(p.word_id.as_str()))
+        .collect();
+
+    Ok(WordbookStats {
+        name: xml_parser::active_wordbook(),
+        total_words: words.len() as u32,
+        tag_count: tags.len() as u32,
+        learned_words: progresses.iter().filter(|p| p.review_count > 0).count() as u32,
+        mastered_words: progresses.iter().filter(|p| p.mastery_level >= 80).count() as u32,
+    })
+}
+
+fn wordbook_exists(name: &str) -> Result<bool> {
+    Ok(list_wordbooks()?.iter().any(|n| n == name))
+}
+
+fn on_settings_changed(app: &AppHandle, change: &SettingsChange) {
+    if !change.touches(SettingsSection::ActiveWordbook) {
+        return;
+    }
+
+    let app = app.clone();
+    let name = change.new.active_wordbook.clone();
+    tauri::async_runtime::spawn(async move {
+        if !xml_parser::set_active_wordbook(&name).await {
+            return;
+        }
+        log::info!("📚 已切换单词本: {}", name);
+
+        if let Err(e) = current_word::next(&app).await {
+            log::warn!("加载新单词本的单词失败: {}", e);
+        }
+        emit_changed(&app, &name);
+    });
+}
+
+/// 单词本文件被外部修改并重新加载后，同步当前单词并通知前端重新加载单词列表
+pub async fn notify_reloaded(app: &AppHandle) {
+    if let Err(e) = current_word::revalidate(app).await {
+        log::warn!("同步当前单词失败: {}", e);
+    }
+    emit_changed(app, &xml_parser::active_wordbook());
+}
+
+// 前端收到事件时当前单词已经选好
+fn emit_changed(app: &AppHandle, name: &str) {
+    if let Err(e) = app.emit("wordbook-changed", name) {
+        log::warn!("发送单词本变更事件失败: {}", e);
+    }
+}
+
+fn find_word_mut<'a>(words: &'a mut [Word], id: &str) -> Result<&'a mut Word> {
+    words
+        .iter_mut()
+        .find(|w| w.id == id)
+        .ok_or_else(|| anyhow!("未找到单词: {}", id))
+}
+
+fn ensure_unique_headword(words: &[Word], headword: &str, except_id: Option<&str>) -> Result<()> {
+    let key = headword.to_lowercase();
+    let duplicate = words
+        .iter()
+        .filter(|w| Some(w.id.as_str()) != except_id)
+        .any(|w| w.word.trim().to_lowercase() == key);
+
+    if duplicate {
+        return Err(anyhow!("单词已存在: {}", headword));
+    }
+
+    Ok(())
+}
+
+fn normalize_input(input: WordInput) -> Result<WordInput> {
+    let word = input.word.trim().to_string();
+    if word.is_empty() {
+        return Err(anyhow!("单词不能为空"));
+    }
+    if word.chars().count() > MAX_WORD_LENGTH {
+        return Err(anyhow!("单词长度不能超过 {} 个字符", MAX_WORD_LENGTH));
+    }
+
+    let trans = input.trans.trim().to_string();
+    if trans.is_empty() {
+        return Err(anyhow!("释义不能为空"));
+    }
+
+    if let Some(difficulty) = input.difficulty {
+        if !(1..=10).contains(&difficulty) {
+            return Err(anyhow!("难度必须在 1-10 之间"));
+        }
+    }
+
+    let examples = input
+        .examples
+        .into_iter()
+        .map(normalize_example)
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(WordInput {
+        word,
+        trans,
+        phonetic: input.phonetic.trim().to_string(),
+        tags: normalize_tags(input.tags)?,
+        examples,
+        difficulty: input.difficulty,
+        note: input.note.trim().to_string(),
+    })
+}
+
+fn normalize_example(example: Example) -> Result<Example> {
+    let source = example.source.trim().to_string();
+    if source.is_empty() {
+        return Err(anyhow!("例句原文不能为空"));
+    }
+
+    Ok(Example {
+        source,
+        trans: example.trans.trim().to_string(),
+    })
+}
+
+fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::new();
+
+    for tag in tags {
+        let tag = tag.trim().to_string();
+        if tag.is_empty() {
+            continue;
+        }
+        // 词汇文件中多个标签以逗号分隔
+        if tag.contains(',') || tag.contains('，') {
+            return Err(anyhow!("标签中不能包含逗号: {}", tag));
+        }
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+
+    Ok(normalized)
+}
@@ -1,17 +1,25 @@
-use crate::models::{Word, Example, WordFilter};
+use crate::models::{Word, Example, WordFilter, WordPage, WordPageRequest, WordProgress, WordSortKey, SortOrder};
 use anyhow::{Result, anyhow};
 use quick_xml::events::Event;
 use quick_xml::reader::Reader;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use regex::Regex;
+use crate::utils::atomic_file;
 
 use std::sync::Mutex;
 use once_cell::sync::Lazy;
 
 static WORDS_CACHE: Lazy<Mutex<Option<Vec<Word>>>> = Lazy::new(|| Mutex::new(None));
+// 串行化对单词列表的修改，避免并发写入互相覆盖
+static WRITE_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+// 当前使用的单词本文件名（位于数据目录的 vocabulary 下）
+static ACTIVE_WORDBOOK: Lazy<Mutex<String>> = Lazy::new(|| Mutex::new(DEFAULT_WORDBOOK.to_string()));
+
+pub const DEFAULT_WORDBOOK: &str = "software_vocabulary.xml";
 
 pub async fn clear_cache() {
     let mut cache = WORDS_CACHE.lock().unwrap();
@@ -19,6 +27,11 @@ pub async fn clear_cache() {
     println!("🧹 单词缓存已清理");
 }
 
+/// 单词列表是否已加载到缓存
+pub fn is_cached() -> bool {
+    WORDS_CACHE.lock().unwrap().is_some()
+}
+
 pub async fn load_words(filter: Option<WordFilter>) -> Result<Vec<Word>> {
     // 先检查缓存是否存在
     let need_load = {
@@ -41,7 +54,7 @@ pub async fn load_words(filter: Option<WordFilter>) -> Result<Vec<Word>> {
     } else {
         let cache = WORDS_CACHE.lock().unwrap();
         let cached_words = cache.as_ref().unwrap();
-        println!("💾 使用已缓存的单词数据，共 {} 个单词", cached_words.len());
+        log::debug!("💾 使用已缓存的单词数据，共 {} 个单词", cached_words.len());
         cached_words.clone()
     };
     
@@ -52,44 +65,138 @@ pub async fn load_words(filter: Option<WordFilter>) -> Result<Vec<Word>> {
         words
     };
     
-    println!("🔍 返回给前端的单词数量: {}", filtered_words.len());
-    if !filtered_words.is_empty() {
-        println!("📖 第一个单词示例: {} - {}", filtered_words[0].word, filtered_words[0].trans);
-    }
+    log::debug!("🔍 返回给前端的单词数量: {}", filtered_words.len());
     
     Ok(filtered_words)
 }
 
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 500;
+
+/// 分页获取单词列表
+///
+/// `cursor` 为上一页最后一个单词的ID，排序结果以ID作为次级键保证稳定；
+/// `progress` 为单词ID到学习进度的映射，单词本文件不保存进度，
+/// 过滤和排序前先用它覆盖单词上的掌握度、复习时间等字段。
+pub async fn list_words(request: WordPageRequest, progress: &HashMap<String, WordProgress>) -> Result<WordPage> {
+    let mut words = load_words(None).await?;
+    for word in &mut words {
+        if let Some(p) = progress.get(&word.id) {
+            word.apply_progress(p);
+        }
+    }
+    if let Some(filter) = &request.filter {
+        words = apply_filter(&words, filter);
+    }
+    
+    let sort_by = request.sort_by.unwrap_or(WordSortKey::Alphabetical);
+    let order = request.order.unwrap_or(SortOrder::Asc);
+    sort_words(&mut words, sort_by, order, progress);
+    
+    let total = words.len() as u32;
+    
+    let start = match request.cursor.as_deref() {
+        Some(cursor) => words
+            .iter()
+            .position(|w| w.id == cursor)
+            .map(|index| index + 1)
+            .ok_or_else(|| anyhow!("无效的分页游标: {}", cursor))?,
+        None => 0,
+    };
+    
+    let limit = request.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE) as usize;
+    let end = (start + limit).min(words.len());
+    
+    let items: Vec<Word> = words.drain(start..end).collect();
+    let next_cursor = if end < total as usize {
+        items.last().map(|w| w.id.clone())
+    } else {
+        None
+    };
+    
+    Ok(WordPage {
+        items,
+        total,
+        next_cursor,
+    })
+}
+
+fn sort_words(words: &mut [Word], sort_by: WordSortKey, order: SortOrder, progress: &HashMap<String, WordProgress>) {
+    use std::cmp::Ordering;
+    
+    words.sort_by(|a, b| {
+        let ordering = match sort_by {
+            WordSortKey::Alphabetical => a.word.to_lowercase().cmp(&b.word.to_lowercase()),
+            WordSortKey::CreatedAt => a.created_at.cmp(&b.created_at),
+            WordSortKey::Mastery => a.mastery_level.cmp(&b.mastery_level),
+            // 从未复习过的单词视为立即到期
+            WordSortKey::NextDue => a.get_next_review_time().cmp(&b.get_next_review_time()),
+            WordSortKey::Difficulty => a.difficulty.cmp(&b.difficulty),
+            WordSortKey::ErrorRate => {
+                let a_rate = progress.get(&a.id).map_or(0.0, WordProgress::error_rate);
+                let b_rate = progress.get(&b.id).map_or(0.0, WordProgress::error_rate);
+                a_rate.partial_cmp(&b_rate).unwrap_or(Ordering::Equal)
+            }
+        };
+        
+        let ordering = match order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        };
+        
+        ordering.then_with(|| a.id.cmp(&b.id))
+    });
+}
+
 pub async fn get_word_by_id(id: &str) -> Result<Option<Word>> {
     let words = load_words(None).await?;
     Ok(words.into_iter().find(|w| w.id == id))
 }
 
 pub async fn update_word_progress(id: &str, progress: u8, is_correct: bool) -> Result<()> {
-    let words_to_save = {
-        let mut cache = WORDS_CACHE.lock().unwrap();
-        
-        if let Some(words) = cache.as_mut() {
-            if let Some(word) = words.iter_mut().find(|w| w.id == id) {
-                word.update_progress(progress, is_correct);
-                Some(words.clone())
-            } else {
-                None
-            }
-        } else {
-            None
+    modify_words(|words| {
+        if let Some(word) = words.iter_mut().find(|w| w.id == id) {
+            word.update_progress(progress, is_correct);
         }
-    };
+        Ok(())
+    })
+    .await
+}
+
+/// 在单词列表上执行修改并持久化
+///
+/// 修改作用于缓存的副本，只有写入文件成功后才替换缓存，
+/// 因此修改失败时缓存和文件保持一致。
+pub async fn modify_words<T>(f: impl FnOnce(&mut Vec<Word>) -> Result<T>) -> Result<T> {
+    let _guard = WRITE_LOCK.lock().await;
     
-    if let Some(words) = words_to_save {
-        save_words_to_file(&words).await?;
-        
-        // 更新缓存
-        let mut cache = WORDS_CACHE.lock().unwrap();
-        *cache = Some(words);
-    }
+    let mut words = load_words(None).await?;
+    let result = f(&mut words)?;
     
-    Ok(())
+    save_words_to_file(&words).await?;
+    
+    let mut cache = WORDS_CACHE.lock().unwrap();
+    *cache = Some(words);
+    
+    Ok(result)
+}
+
+/// 用外部修改后的文件内容替换缓存，返回单词数量
+pub async fn reload_from_content(content: &str) -> Result<usize> {
+    let _guard = WRITE_LOCK.lock().await;
+    
+    let words = parse_xml(content)?;
+    let count = words.len();
+    
+    let mut cache = WORDS_CACHE.lock().unwrap();
+    *cache = Some(words);
+    
+    Ok(count)
+}
+
+/// 将当前缓存写回词汇文件
+pub async fn save_cache() -> Result<()> {
+    modify_words(|_| Ok(())).await
 }
 
 pub async fn search_words(query: &str, limit: u32) -> Result<Vec<Word>> {
@@ -127,6 +234,14 @@ pub async fn search_words(query: &str, limit: u32) -> Result<Vec<Word>> {
 async fn load_words_from_file() -> Result<Vec<Word>> {
     println!("📁 开始加载词汇文件...");
     
+    // 用户数据目录中的词汇文件包含用户的编辑和学习进度，存在时优先使用
+    let user_vocab_path = get_vocabulary_file_path()?;
+    if user_vocab_path.exists() || atomic_file::backup_path(&user_vocab_path).exists() {
+        log::info!("📁 从用户数据目录加载: {:?}", user_vocab_path);
+        // 文件损坏时自动从 .bak 恢复
+        return atomic_file::read_with_recovery(&user_vocab_path, parse_xml);
+    }
+    
     // 优先使用Tauri资源文件（适用于打包后的应用）
     if let Ok(content) = load_from_tauri_resource().await {
         println!("✅ 从Tauri资源文件加载成功");
@@ -160,8 +275,7 @@ async fn load_words_from_file() -> Result<Vec<Word>> {
     
     // 最后尝试从数据目录读取或创建
     println!("📁 尝试从用户数据目录加载或创建文件...");
-    let data_dir = get_data_directory()?;
-    let data_vocab_path = data_dir.join("vocabulary").join("software_vocabulary.xml");
+    let data_vocab_path = get_vocabulary_file_path()?;
     
     if !data_vocab_path.exists() {
         create_default_vocabulary_file(&data_vocab_path).await?;
@@ -174,22 +288,20 @@ async fn load_words_from_file() -> Result<Vec<Word>> {
 }
 
 async fn save_words_to_file(words: &[Word]) -> Result<()> {
-    let data_dir = get_data_directory()?;
-    let vocab_path = data_dir.join("vocabulary").join("software_vocabulary.xml");
-    
-    // 确保目录存在
-    if let Some(parent) = vocab_path.parent() {
-        fs::create_dir_all(parent)?;
-    }
+    let vocab_path = get_vocabulary_file_path()?;
     
     let xml_content = generate_xml_content(words)?;
-    fs::write(&vocab_path, xml_content)
+    atomic_file::write_atomic(&vocab_path, xml_content)
         .map_err(|e| anyhow!("无法保存词汇文件: {}", e))?;
     
     Ok(())
 }
 
 async fn parse_xml_content(content: &str) -> Result<Vec<Word>> {
+    parse_xml(content)
+}
+
+fn parse_xml(content: &str) -> Result<Vec<Word>> {
     println!("📄 开始解析XML内容，文件大小: {} 字节", content.len());
     
     let mut reader = Reader::from_str(content);
@@ -200,6 +312,8 @@ async fn parse_xml_content(content: &str) -> Result<Vec<Word>> {
     let mut current_example: Option<Example> = None;
     let mut buf = Vec::new();
     let mut text_content = String::new();
+    // 用于识别写入中断导致的截断文件
+    let mut reached_root_end = false;
     
     loop {
         match reader.read_event_into(&mut buf) {
@@ -257,9 +371,27 @@ async fn parse_xml_content(content: &str) -> Result<Vec<Word>> {
                             word.phonetic = clean_html_tags(&text_content);
                         }
                     }
+                    "id" => {
+                        if let Some(ref mut word) = current_word {
+                            let id = text_content.trim();
+                            if !id.is_empty() {
+                                word.id = id.to_string();
+                            }
+                        }
+                    }
                     "tags" => {
                         if let Some(ref mut word) = current_word {
-                            word.tags = vec![text_content.trim().to_string()];
+                            // 多个标签以逗号分隔
+                            word.tags = text_content
+                                .split(|c| c == ',' || c == '，')
+                                .map(|tag| tag.trim().to_string())
+                                .filter(|tag| !tag.is_empty())
+                                .collect();
+                        }
+                    }
+                    "difficulty" => {
+                        if let Some(ref mut word) = current_word {
+                            word.difficulty = text_content.trim().parse().unwrap_or(5);
                         }
                     }
                     "progress" => {
@@ -272,6 +404,17 @@ async fn parse_xml_content(content: &str) -> Result<Vec<Word>> {
                             word.note = clean_html_tags(&text_content);
                         }
                     }
+                    // 旧文件没有时间字段，保留加载时的时间，下次保存后固定下来
+                    "created_at" => {
+                        if let (Some(ref mut word), Some(time)) = (&mut current_word, parse_time(&text_content)) {
+                            word.created_at = time;
+                        }
+                    }
+                    "updated_at" => {
+                        if let (Some(ref mut word), Some(time)) = (&mut current_word, parse_time(&text_content)) {
+                            word.updated_at = time;
+                        }
+                    }
                     "source" => {
                         if let Some(ref mut example) = current_example {
                             example.source = text_content.trim().to_string();
@@ -282,6 +425,7 @@ async fn parse_xml_content(content: &str) -> Result<Vec<Word>> {
                             word.examples.push(example);
                         }
                     }
+                    "wordbook" => reached_root_end = true,
                     "item" => {
                         if let Some(mut word) = current_word.take() {
                             // 如果没有ID，生成一个
@@ -289,12 +433,6 @@ async fn parse_xml_content(content: &str) -> Result<Vec<Word>> {
                                 word.id = Uuid::new_v4().to_string();
                             }
                             
-                            // 设置创建时间
-                            if word.created_at == word.updated_at {
-                                word.created_at = Utc::now();
-                                word.updated_at = Utc::now();
-                            }
-                            
                             println!("✅ 解析单词: {} - {}", word.word, word.trans);
                             words.push(word);
                         }
@@ -311,6 +449,10 @@ async fn parse_xml_content(content: &str) -> Result<Vec<Word>> {
         buf.clear();
     }
     
+    if !reached_root_end {
+        return Err(anyhow!("XML文件不完整，缺少 </wordbook> 结束标签"));
+    }
+    
     println!("🎉 XML解析完成，共解析到 {} 个单词", words.len());
     Ok(words)
 }
@@ -324,18 +466,22 @@ fn generate_xml_content(words: &[Word]) -> Result<String> {
     for word in words {
         output.extend_from_slice(b"    <item>\n");
         
+        output.extend_from_slice(format!("        <id>{}</id>\n", escape_xml(&word.id)).as_bytes());
         output.extend_from_slice(format!("        <word>{}</word>\n", escape_xml(&word.word)).as_bytes());
-        output.extend_from_slice(format!("        <trans><![CDATA[{}]]></trans>\n", word.trans).as_bytes());
-        output.extend_from_slice(format!("        <phonetic><![CDATA[{}]]></phonetic>\n", word.phonetic).as_bytes());
+        output.extend_from_slice(format!("        <trans>{}</trans>\n", cdata(&word.trans)).as_bytes());
+        output.extend_from_slice(format!("        <phonetic>{}</phonetic>\n", cdata(&word.phonetic)).as_bytes());
         
         if !word.tags.is_empty() {
-            output.extend_from_slice(format!("        <tags>{}</tags>\n", escape_xml(&word.tags[0])).as_bytes());
+            output.extend_from_slice(format!("        <tags>{}</tags>\n", escape_xml(&word.tags.join(","))).as_bytes());
         }
         
         output.extend_from_slice(format!("        <progress>{}</progress>\n", word.progress).as_bytes());
+        output.extend_from_slice(format!("        <difficulty>{}</difficulty>\n", word.difficulty).as_bytes());
+        output.extend_from_slice(format!("        <created_at>{}</created_at>\n", word.created_at.to_rfc3339()).as_bytes());
+        output.extend_from_slice(format!("        <updated_at>{}</updated_at>\n", word.updated_at.to_rfc3339()).as_bytes());
         
         if !word.note.is_empty() {
-            output.extend_from_slice(format!("        <note><![CDATA[{}]]></note>\n", word.note).as_bytes());
+            output.extend_from_slice(format!("        <note>{}</note>\n", cdata(&word.note)).as_bytes());
         }
         
         if !word.examples.is_empty() {
@@ -343,7 +489,7 @@ fn generate_xml_content(words: &[Word]) -> Result<String> {
             for example in &word.examples {
                 output.extend_from_slice(b"            <example>\n");
                 output.extend_from_slice(format!("                <source>{}</source>\n", escape_xml(&example.source)).as_bytes());
-                output.extend_from_slice(format!("                <trans><![CDATA[{}]]></trans>\n", example.trans).as_bytes());
+                output.extend_from_slice(format!("                <trans>{}</trans>\n", cdata(&example.trans)).as_bytes());
                 output.extend_from_slice(b"            </example>\n");
             }
             output.extend_from_slice(b"        </examples>\n");
@@ -464,6 +610,17 @@ fn clean_html_tags(text: &str) -> String {
     decoded.trim().to_string()
 }
 
+/// 包装为 CDATA 段；内容中的 `]]>` 会提前结束 CDATA，拆到两个相邻的段中
+fn cdata(text: &str) -> String {
+    format!("<![CDATA[{}]]>", text.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+fn parse_time(text: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(text.trim())
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
 fn escape_xml(text: &str) -> String {
     text.replace('&', "&amp;")
         .replace('<', "&lt;")
@@ -478,6 +635,36 @@ fn get_data_directory() -> Result<std::path::PathBuf> {
         .ok_or_else(|| anyhow!("无法获取数据目录"))
 }
 
+pub fn get_vocabulary_directory() -> Result<PathBuf> {
+    get_data_directory().map(|dir| dir.join("vocabulary"))
+}
+
+pub fn get_vocabulary_file_path() -> Result<PathBuf> {
+    let name = active_wordbook();
+    get_vocabulary_directory().map(|dir| dir.join(name))
+}
+
+pub fn active_wordbook() -> String {
+    ACTIVE_WORDBOOK.lock().unwrap().clone()
+}
+
+/// 切换当前单词本，返回是否发生了变化；切换后清空缓存，下次读取时加载新的文件
+pub async fn set_active_wordbook(name: &str) -> bool {
+    // 等待进行中的修改写完，避免旧单词本的内容写入新单词本的文件
+    let _guard = WRITE_LOCK.lock().await;
+
+    {
+        let mut active = ACTIVE_WORDBOOK.lock().unwrap();
+        if *active == name {
+            return false;
+        }
+        *active = name.to_string();
+    }
+
+    clear_cache().await;
+    true
+}
+
 async fn load_from_tauri_resource() -> Result<String> {
     // 在打包后的应用中，资源文件会被嵌入到可执行文件中
     // 这里我们尝试从资源目录读取
@@ -541,7 +728,7 @@ async fn create_default_vocabulary_file(path: &Path) -> Result<()> {
         let default_content = r#"<?xml version="1.0" encoding="UTF-8"?>
 <wordbook>
 </wordbook>"#;
-        fs::write(path, default_content)?;
+        atomic_file::write_atomic(path, default_content)?;
     }
     
     Ok(())
@@ -0,0 +1,33 @@
+/// 距离用户最后一次键盘或鼠标操作的秒数，无法获取时返回 None
+pub fn idle_seconds() -> Option<u64> {
+    match user_idle::UserIdle::get_time() {
+        Ok(idle) => Some(idle.as_seconds()),
+        Err(e) => {
+            log::debug!("获取空闲时间失败: {}", e);
+            None
+        }
+    }
+}
+
+/// 是否有全屏程序（游戏、演示文稿等）正在前台运行
+#[cfg(windows)]
+pub fn is_fullscreen_app_running() -> bool {
+    use windows_sys::Win32::UI::Shell::{
+        SHQueryUserNotificationState, QUNS_BUSY, QUNS_PRESENTATION_MODE, QUNS_RUNNING_D3D_FULL_SCREEN,
+    };
+
+    let mut state = 0;
+    // SAFETY: state 是有效的输出指针
+    let result = unsafe { SHQueryUserNotificationState(&mut state) };
+    if result != 0 {
+        return false;
+    }
+
+    matches!(state, QUNS_BUSY | QUNS_RUNNING_D3D_FULL_SCREEN | QUNS_PRESENTATION_MODE)
+}
+
+/// 其他平台暂不支持检测全屏程序
+#[cfg(not(windows))]
+pub fn is_fullscreen_app_running() -> bool {
+    false
+}
@@ -0,0 +1,272 @@
+use anyhow::{Result, anyhow};
+use once_cell::sync::Lazy;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+// 每个文件最近一次由本程序写入的内容摘要，用于区分外部修改
+static LAST_WRITES: Lazy<Mutex<HashMap<PathBuf, u64>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// 临时文件序号，与进程 id 一起保证并发写入同一文件时各自使用不同的临时文件
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// 原子写入文件
+///
+/// 先写入同目录下的临时文件并 fsync，再通过 rename 替换目标文件，
+/// 写入过程中崩溃或断电不会留下被截断的文件。被替换的旧版本保留为 `.bak`。
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)
+                .map_err(|e| anyhow!("无法创建目录 {:?}: {}", parent, e))?;
+        }
+    }
+
+    let temp = temp_path(path);
+    write_and_sync(&temp, contents.as_ref())?;
+
+    // 滚动备份：保留上一个版本
+    if path.exists() {
+        let backup_path = backup_path(path);
+        let backup_temp_path = temp_path(&backup_path);
+        let result = fs::copy(path, &backup_temp_path)
+            .and_then(|_| File::open(&backup_temp_path)?.sync_all())
+            .and_then(|_| fs::rename(&backup_temp_path, &backup_path));
+        if let Err(e) = result {
+            // 备份失败不影响本次写入
+            log::warn!("创建备份文件失败 {:?}: {}", backup_path, e);
+            let _ = fs::remove_file(&backup_temp_path);
+        }
+    }
+
+    if let Err(e) = fs::rename(&temp, path) {
+        let _ = fs::remove_file(&temp);
+        return Err(anyhow!("无法替换文件 {:?}: {}", path, e));
+    }
+
+    sync_parent_directory(path);
+    LAST_WRITES.lock().unwrap().insert(path.to_path_buf(), digest(contents.as_ref()));
+    Ok(())
+}
+
+/// 判断文件内容是否与本程序最近一次写入的内容一致
+pub fn is_own_write(path: &Path, contents: &[u8]) -> bool {
+    LAST_WRITES
+        .lock()
+        .unwrap()
+        .get(path)
+        .map(|last| *last == digest(contents))
+        .unwrap_or(false)
+}
+
+fn digest(contents: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 读取并解析文件，文件损坏或缺失时尝试从 `.bak` 恢复
+///
+/// 恢复成功时会用备份内容覆盖损坏的文件；两者都不可用时返回原文件的错误。
+pub fn read_with_recovery<T>(path: &Path, parse: impl Fn(&str) -> Result<T>) -> Result<T> {
+    let primary_error = match fs::read_to_string(path) {
+        Ok(content) => match parse(&content) {
+            Ok(value) => return Ok(value),
+            Err(e) => anyhow!("文件 {:?} 已损坏: {}", path, e),
+        },
+        Err(e) => anyhow!("无法读取文件 {:?}: {}", path, e),
+    };
+
+    let backup_path = backup_path(path);
+    if !backup_path.exists() {
+        return Err(primary_error);
+    }
+
+    log::warn!("⚠️ {}，尝试从备份恢复: {:?}", primary_error, backup_path);
+
+    let backup_content = fs::read_to_string(&backup_path)
+        .map_err(|e| anyhow!("{}；读取备份失败: {}", primary_error, e))?;
+    let value = parse(&backup_content)
+        .map_err(|e| anyhow!("{}；备份文件同样损坏: {}", primary_error, e))?;
+
+    // 损坏的文件另存一份以便排查，再用备份覆盖它；不滚动备份以免覆盖唯一可用的版本
+    if path.exists() {
+        if let Err(e) = preserve_aside(path, "corrupt") {
+            log::warn!("保留损坏文件失败: {}", e);
+        }
+    }
+    let temp = temp_path(path);
+    write_and_sync(&temp, backup_content.as_bytes())?;
+    fs::rename(&temp, path)
+        .map_err(|e| anyhow!("无法从备份恢复文件 {:?}: {}", path, e))?;
+    sync_parent_directory(path);
+    LAST_WRITES.lock().unwrap().insert(path.to_path_buf(), digest(backup_content.as_bytes()));
+
+    log::info!("✅ 已从备份恢复文件: {:?}", path);
+    Ok(value)
+}
+
+/// 将无法使用的文件重命名为 `<文件名>.<tag>-<时间戳>`，返回新的路径
+pub fn preserve_aside(path: &Path, tag: &str) -> Result<PathBuf> {
+    let timestamp = chrono::Local::now().format("%Y%m%d%H%M%S");
+    let aside_path = sibling_path(path, &format!("{}-{}", tag, timestamp));
+    fs::rename(path, &aside_path)
+        .map_err(|e| anyhow!("无法保留文件 {:?}: {}", path, e))?;
+    log::warn!("⚠️ 已将无法使用的文件另存为: {:?}", aside_path);
+    Ok(aside_path)
+}
+
+pub fn backup_path(path: &Path) -> PathBuf {
+    sibling_path(path, "bak")
+}
+
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_default();
+    file_name.push(".");
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let sequence = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+    sibling_path(path, &format!("{}-{}.tmp", std::process::id(), sequence))
+}
+
+fn write_and_sync(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut file = File::create(path)
+        .map_err(|e| anyhow!("无法创建临时文件 {:?}: {}", path, e))?;
+    file.write_all(contents)
+        .map_err(|e| anyhow!("写入临时文件失败 {:?}: {}", path, e))?;
+    file.sync_all()
+        .map_err(|e| anyhow!("同步临时文件失败 {:?}: {}", path, e))?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn sync_parent_directory(path: &Path) {
+    // rename 的持久化依赖目录项落盘
+    if let Some(parent) = path.parent() {
+        if let Ok(dir) = File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent_directory(_path: &Path) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("word-pony-atomic-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn parse_number(content: &str) -> Result<u32> {
+        Ok(content.trim().parse()?)
+    }
+
+    #[test]
+    fn write_replaces_file_and_keeps_previous_version() {
+        let dir = temp_dir("write");
+        let path = dir.join("data.txt");
+
+        write_atomic(&path, "1").unwrap();
+        assert!(!backup_path(&path).exists());
+        write_atomic(&path, "2").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "2");
+        assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), "1");
+        assert!(is_own_write(&path, b"2"));
+        assert!(!is_own_write(&path, b"1"));
+        // 临时文件不会残留
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn write_creates_missing_directories() {
+        let dir = temp_dir("nested");
+        let path = dir.join("a").join("b").join("data.txt");
+
+        write_atomic(&path, "1").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn corrupt_file_is_recovered_from_backup() {
+        let dir = temp_dir("corrupt");
+        let path = dir.join("data.txt");
+        write_atomic(&path, "1").unwrap();
+        write_atomic(&path, "2").unwrap();
+        fs::write(&path, "not a number").unwrap();
+
+        assert_eq!(read_with_recovery(&path, parse_number).unwrap(), 1);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1");
+        // 损坏的文件另存一份
+        let preserved = fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .any(|entry| entry.file_name().to_string_lossy().starts_with("data.txt.corrupt-"));
+        assert!(preserved);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn missing_file_is_recovered_from_backup() {
+        let dir = temp_dir("missing");
+        let path = dir.join("data.txt");
+        write_atomic(&path, "1").unwrap();
+        write_atomic(&path, "2").unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(read_with_recovery(&path, parse_number).unwrap(), 1);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unrecoverable_file_reports_error() {
+        let dir = temp_dir("unrecoverable");
+        let path = dir.join("data.txt");
+        fs::write(&path, "bad").unwrap();
+        assert!(read_with_recovery(&path, parse_number).is_err());
+
+        fs::write(backup_path(&path), "also bad").unwrap();
+        assert!(read_with_recovery(&path, parse_number).is_err());
+        // 备份同样损坏时保留原文件
+        assert_eq!(fs::read_to_string(&path).unwrap(), "bad");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn valid_file_is_read_without_touching_backup() {
+        let dir = temp_dir("valid");
+        let path = dir.join("data.txt");
+        write_atomic(&path, "1").unwrap();
+        write_atomic(&path, "2").unwrap();
+
+        assert_eq!(read_with_recovery(&path, parse_number).unwrap(), 2);
+        assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), "1");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,4 +1,5 @@
 use crate::models::Settings;
+use crate::utils::{atomic_file, settings_migration};
 use anyhow::{Result, anyhow};
 use std::fs;
 use std::path::PathBuf;
@@ -6,14 +7,26 @@ use std::path::PathBuf;
 pub async fn load_settings() -> Result<Settings> {
     let config_path = get_config_file_path()?;
     
-    if config_path.exists() {
-        let content = fs::read_to_string(&config_path)
-            .map_err(|e| anyhow!("无法读取配置文件: {}", e))?;
-        
-        let settings: Settings = serde_json::from_str(&content)
-            .map_err(|e| anyhow!("配置文件格式错误: {}", e))?;
-        
-        Ok(settings)
+    if config_path.exists() || atomic_file::backup_path(&config_path).exists() {
+        // 配置文件损坏时自动从 .bak 恢复，旧版本配置自动迁移
+        match atomic_file::read_with_recovery(&config_path, settings_migration::parse_settings) {
+            Ok(loaded) => {
+                if loaded.migrated {
+                    save_settings(&loaded.settings).await?;
+                }
+                Ok(loaded.settings)
+            }
+            Err(e) => {
+                // 无法识别的配置文件另存后使用默认配置，避免启动失败
+                log::error!("❌ 配置文件无法加载，将使用默认配置: {}", e);
+                if config_path.exists() {
+                    atomic_file::preserve_aside(&config_path, "unreadable")?;
+                }
+                let default_settings = Settings::default();
+                save_settings(&default_settings).await?;
+                Ok(default_settings)
+            }
+        }
     } else {
         // 创建默认配置
         let default_settings = Settings::default();
@@ -25,16 +38,10 @@ pub async fn load_settings() -> Result<Settings> {
 pub async fn save_settings(settings: &Settings) -> Result<()> {
     let config_path = get_config_file_path()?;
     
-    // 确保目录存在
-    if let Some(parent) = config_path.parent() {
-        fs::create_dir_all(parent)
-            .map_err(|e| anyhow!("无法创建配置目录: {}", e))?;
-    }
-    
     let json_content = serde_json::to_string_pretty(settings)
         .map_err(|e| anyhow!("序列化配置失败: {}", e))?;
     
-    fs::write(&config_path, json_content)
+    atomic_file::write_atomic(&config_path, json_content)
         .map_err(|e| anyhow!("无法保存配置文件: {}", e))?;
     
     log::info!("配置已保存到: {:?}", config_path);
@@ -86,7 +93,7 @@ pub async fn backup_user_data(backup_path: &str) -> Result<()> {
     });
     
     let backup_info_path = backup_dir.join("backup_info.json");
-    fs::write(&backup_info_path, serde_json::to_string_pretty(&backup_info)?)
+    atomic_file::write_atomic(&backup_info_path, serde_json::to_string_pretty(&backup_info)?)
         .map_err(|e| anyhow!("创建备份信息文件失败: {}", e))?;
     
     log::info!("用户数据已备份到: {:?}", backup_dir);
@@ -155,11 +162,11 @@ pub async fn restore_user_data(backup_path: &str) -> Result<()> {
     Ok(())
 }
 
-fn get_config_file_path() -> Result<PathBuf> {
+pub fn get_config_file_path() -> Result<PathBuf> {
     get_data_directory().map(|dir| dir.join("settings.json"))
 }
 
-fn get_data_directory() -> Result<PathBuf> {
+pub fn get_data_directory() -> Result<PathBuf> {
     dirs::config_dir()
         .or_else(|| dirs::data_dir())
         .map(|dir| dir.join("WordPony"))
@@ -207,12 +214,12 @@ pub async fn get_app_data_info() -> Result<serde_json::Value> {
 pub async fn export_settings(export_path: &str) -> Result<()> {
     let settings = load_settings().await?;
     let json_content = serde_json::to_string_pretty(&settings)?;
-    fs::write(export_path, json_content)?;
+    atomic_file::write_atomic(std::path::Path::new(export_path), json_content)?;
     Ok(())
 }
 
 pub async fn import_settings(import_path: &str) -> Result<()> {
     let content = fs::read_to_string(import_path)?;
-    let settings: Settings = serde_json::from_str(&content)?;
+    let settings = settings_migration::parse_settings(&content)?.settings;
     save_settings(&settings).await
 } 
\ No newline at end of file
@@ -1,49 +1,158 @@
+use crate::models::{AppAction, HotkeyConfig, HotkeyScope};
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::str::FromStr;
+use tauri_plugin_global_shortcut::{Modifiers, Shortcut};
+
+// 生成替代快捷键时尝试的修饰键组合，按推荐程度排序
+const SUGGESTED_MODIFIERS: [&str; 4] = ["CmdOrCtrl+Shift", "CmdOrCtrl+Alt", "Alt+Shift", "CmdOrCtrl+Alt+Shift"];
+const MAX_SUGGESTIONS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyIssueKind {
+    Invalid,   // 无法解析
+    Unsafe,    // 没有 Ctrl/Alt/Cmd 修饰键，全局注册会吞掉普通输入
+    Duplicate, // 与其他操作使用了同一组合键
+    Conflict,  // 已被系统或其他程序占用
+}
+
+/// 快捷键配置中的问题，附带可用的替代组合
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeyIssue {
+    pub action: AppAction,
+    pub shortcut: String,
+    pub kind: HotkeyIssueKind,
+    pub message: String,
+    pub suggestions: Vec<String>,
+}
 
 pub fn parse_shortcut(shortcut: &str) -> Result<(Vec<String>, String)> {
     let parts: Vec<&str> = shortcut.split('+').collect();
-    
+
     if parts.is_empty() {
         return Err(anyhow::anyhow!("无效的快捷键格式"));
     }
-    
+
     let key = parts.last().unwrap().to_string();
     let modifiers: Vec<String> = parts[..parts.len()-1]
         .iter()
         .map(|s| s.to_string())
         .collect();
-    
+
     Ok((modifiers, key))
 }
 
+/// 按全局快捷键插件支持的按键集合解析快捷键
+pub fn parse(shortcut: &str) -> Result<Shortcut> {
+    if shortcut.trim().is_empty() {
+        return Err(anyhow::anyhow!("快捷键不能为空"));
+    }
+
+    Shortcut::from_str(shortcut).map_err(|e| anyhow::anyhow!("无效的快捷键 {}: {}", shortcut, e))
+}
+
+/// 全局快捷键至少需要 Ctrl、Alt 或 Cmd 之一，否则会拦截其他程序中的正常输入
+/// （单独的 Shift 同样不够，Shift+字母 就是大写字母）
+pub fn is_unsafe(shortcut: &Shortcut) -> bool {
+    !shortcut.mods.intersects(Modifiers::CONTROL | Modifiers::ALT | Modifiers::SUPER)
+}
+
 pub fn validate_shortcut(shortcut: &str) -> Result<()> {
-    let (modifiers, key) = parse_shortcut(shortcut)?;
-    
-    // 验证修饰键
-    for modifier in &modifiers {
-        match modifier.as_str() {
-            "Ctrl" | "CmdOrCtrl" | "Cmd" | "Alt" | "Shift" | "Super" => {}
-            _ => return Err(anyhow::anyhow!("无效的修饰键: {}", modifier)),
+    let parsed = parse(shortcut)?;
+
+    if is_unsafe(&parsed) {
+        return Err(anyhow::anyhow!(
+            "全局快捷键 {} 缺少 Ctrl/Alt/Cmd 修饰键，会影响其他程序的正常输入",
+            shortcut
+        ));
+    }
+
+    Ok(())
+}
+
+/// 检查快捷键配置：无法解析、不安全以及重复的组合键
+///
+/// 安全性只约束全局快捷键；窗口内快捷键与全局快捷键相同时同样视为重复，
+/// 因为全局快捷键会先被系统截获。
+pub fn check_config(config: &HotkeyConfig) -> Vec<HotkeyIssue> {
+    let mut issues = Vec::new();
+    let mut seen: HashMap<Shortcut, AppAction> = HashMap::new();
+    let taken = parsed_shortcuts(config);
+
+    for (action, shortcut, scope) in config.entries() {
+        let parsed = match parse(shortcut) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                issues.push(issue(action, shortcut, HotkeyIssueKind::Invalid, e.to_string(), &taken));
+                continue;
+            }
+        };
+
+        if scope == HotkeyScope::Global {
+            if let Err(e) = validate_shortcut(shortcut) {
+                issues.push(issue(action, shortcut, HotkeyIssueKind::Unsafe, e.to_string(), &taken));
+                continue;
+            }
+        }
+
+        match seen.get(&parsed) {
+            Some(other) => {
+                let message = format!("快捷键 {} 已被「{}」使用", shortcut, other.label());
+                issues.push(issue(action, shortcut, HotkeyIssueKind::Duplicate, message, &taken));
+            }
+            None => {
+                seen.insert(parsed, action);
+            }
         }
     }
-    
-    // 验证主键
-    if key.is_empty() {
-        return Err(anyhow::anyhow!("快捷键不能为空"));
+
+    issues
+}
+
+/// 为有问题的快捷键生成替代组合：保留原来的主键，换用更安全的修饰键，
+/// 并跳过 `taken` 中已使用的组合
+pub fn suggest_alternatives(shortcut: &str, taken: &[Shortcut]) -> Vec<String> {
+    let key = match parse_shortcut(shortcut) {
+        Ok((_, key)) if !key.trim().is_empty() => key.trim().to_string(),
+        _ => return Vec::new(),
+    };
+
+    SUGGESTED_MODIFIERS
+        .iter()
+        .map(|modifiers| format!("{}+{}", modifiers, key))
+        .filter(|candidate| match parse(candidate) {
+            Ok(parsed) => !taken.contains(&parsed),
+            Err(_) => false,
+        })
+        .take(MAX_SUGGESTIONS)
+        .collect()
+}
+
+/// 配置中所有可以解析的快捷键
+pub fn parsed_shortcuts(config: &HotkeyConfig) -> Vec<Shortcut> {
+    config
+        .entries()
+        .into_iter()
+        .filter_map(|(_, shortcut, _)| parse(shortcut).ok())
+        .collect()
+}
+
+fn issue(action: AppAction, shortcut: &str, kind: HotkeyIssueKind, message: String, taken: &[Shortcut]) -> HotkeyIssue {
+    HotkeyIssue {
+        action,
+        shortcut: shortcut.to_string(),
+        kind,
+        message,
+        suggestions: suggest_alternatives(shortcut, taken),
     }
-    
-    Ok(())
 }
 
 pub fn get_default_hotkeys() -> HashMap<String, String> {
-    let mut hotkeys = HashMap::new();
-    
-    hotkeys.insert("toggle_window".to_string(), "CmdOrCtrl+Shift+W".to_string());
-    hotkeys.insert("next_word".to_string(), "CmdOrCtrl+Right".to_string());
-    hotkeys.insert("prev_word".to_string(), "CmdOrCtrl+Left".to_string());
-    hotkeys.insert("play_pronunciation".to_string(), "Space".to_string());
-    hotkeys.insert("pause_learning".to_string(), "CmdOrCtrl+P".to_string());
-    
-    hotkeys
-} 
\ No newline at end of file
+    HotkeyConfig::default()
+        .entries()
+        .into_iter()
+        .map(|(action, shortcut, _)| (action.id().to_string(), shortcut.to_string()))
+        .collect()
+}
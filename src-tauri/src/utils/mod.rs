@@ -1,2 +1,6 @@
 pub mod config;
-pub mod hotkey; 
\ No newline at end of file
+pub mod hotkey;
+pub mod atomic_file;
+pub mod settings_migration;
+pub mod stores;
+pub mod activity;
\ No newline at end of file
@@ -0,0 +1,269 @@
+use crate::models::{AppAction, HotkeyConfig, HotkeyScope, Settings};
+use crate::utils::hotkey;
+use anyhow::{Result, anyhow};
+use serde_json::{Map, Value};
+
+/// 当前配置文件的结构版本，修改 `Settings` 的字段含义或名称时递增并添加迁移函数
+pub const CURRENT_SETTINGS_VERSION: u32 = 4;
+
+type Migration = fn(&mut Map<String, Value>) -> Result<()>;
+
+// MIGRATIONS[n] 负责把版本 n 升级到版本 n + 1
+const MIGRATIONS: &[Migration] = &[
+    migrate_v0_to_v1,
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+    migrate_v3_to_v4,
+];
+
+// v3 及更早版本的快捷键字段：(字段名, v3 默认值, 对应的操作)
+const LEGACY_HOTKEYS: [(&str, &str, AppAction); 5] = [
+    ("toggle_window", "CmdOrCtrl+Shift+W", AppAction::ToggleWindow),
+    ("next_word", "CmdOrCtrl+Right", AppAction::NextWord),
+    ("prev_word", "CmdOrCtrl+Left", AppAction::PrevWord),
+    ("play_pronunciation", "CmdOrCtrl+Shift+Space", AppAction::SpeakWord),
+    ("pause_learning", "CmdOrCtrl+P", AppAction::ToggleAutoSwitch),
+];
+
+pub struct LoadedSettings {
+    pub settings: Settings,
+    pub migrated: bool, // 是否经过了迁移，需要写回磁盘
+}
+
+/// 解析配置文件内容，必要时依次执行迁移
+///
+/// 新增字段由 serde 默认值补齐；版本号高于当前程序支持的版本时返回错误。
+pub fn parse_settings(content: &str) -> Result<LoadedSettings> {
+    let value: Value = serde_json::from_str(content)
+        .map_err(|e| anyhow!("配置文件格式错误: {}", e))?;
+
+    let (value, migrated) = migrate(value)?;
+
+    let settings: Settings = serde_json::from_value(value)
+        .map_err(|e| anyhow!("配置文件格式错误: {}", e))?;
+
+    Ok(LoadedSettings { settings, migrated })
+}
+
+fn migrate(value: Value) -> Result<(Value, bool)> {
+    let mut object = match value {
+        Value::Object(object) => object,
+        _ => return Err(anyhow!("配置文件格式错误: 根节点必须是对象")),
+    };
+
+    // 早期版本的配置文件没有 version 字段，视为版本 0
+    let mut version = match object.get("version") {
+        None => 0,
+        Some(v) => v
+            .as_u64()
+            .map(|v| v as u32)
+            .ok_or_else(|| anyhow!("配置文件版本号无效: {}", v))?,
+    };
+
+    if version > CURRENT_SETTINGS_VERSION {
+        return Err(anyhow!(
+            "配置文件版本 {} 高于当前支持的版本 {}",
+            version,
+            CURRENT_SETTINGS_VERSION
+        ));
+    }
+
+    let migrated = version < CURRENT_SETTINGS_VERSION;
+    while version < CURRENT_SETTINGS_VERSION {
+        MIGRATIONS[version as usize](&mut object)
+            .map_err(|e| anyhow!("配置迁移 v{} -> v{} 失败: {}", version, version + 1, e))?;
+        version += 1;
+        object.insert("version".to_string(), Value::from(version));
+        log::info!("🔄 配置已迁移到版本 {}", version);
+    }
+
+    Ok((Value::Object(object), migrated))
+}
+
+/// v0 -> v1：引入 version 字段，缺失的字段由 serde 默认值补齐
+fn migrate_v0_to_v1(_settings: &mut Map<String, Value>) -> Result<()> {
+    Ok(())
+}
+
+/// v1 -> v2：字符串选项改为枚举
+///
+/// 旧版本会静默忽略无效值，这里统一转为小写，并把无法识别的值重置为默认值。
+fn migrate_v1_to_v2(settings: &mut Map<String, Value>) -> Result<()> {
+    normalize_choice(settings, "window_size", "ratio_preset", &["phone", "square", "golden", "widescreen", "custom"], "phone");
+    normalize_choice(settings, "theme", "mode", &["light", "dark", "auto"], "auto");
+    normalize_choice(settings, "learning", "review_mode", &["smart", "sequential", "random"], "smart");
+    normalize_choice(settings, "learning", "difficulty_preference", &["easy", "medium", "hard", "mixed"], "mixed");
+    Ok(())
+}
+
+/// v2 -> v3：全局快捷键必须带 Ctrl/Alt/Cmd 修饰键
+///
+/// 旧的默认值 `Space` 会拦截所有程序中的空格输入，不安全或无法解析的快捷键重置为默认值。
+fn migrate_v2_to_v3(settings: &mut Map<String, Value>) -> Result<()> {
+    let Some(Value::Object(hotkeys)) = settings.get_mut("hotkeys") else {
+        return Ok(());
+    };
+
+    for (field, default, _) in LEGACY_HOTKEYS {
+        let Some(value) = hotkeys.get_mut(field) else {
+            continue;
+        };
+
+        let valid = match value.as_str() {
+            Some(shortcut) => shortcut.trim().is_empty() || hotkey::validate_shortcut(shortcut).is_ok(),
+            None => false,
+        };
+        if !valid {
+            log::warn!("⚠️ 快捷键 hotkeys.{} 的值 {} 不安全或无效，已重置为 {}", field, value, default);
+            *value = Value::String(default.to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// v3 -> v4：快捷键改为按操作绑定，并区分全局与窗口内生效
+///
+/// 原有的五个快捷键保留为全局快捷键；从未生效的 `pause_learning` 改为窗口内的「自动切换」，
+/// 避免全局占用 Ctrl+P。没有配置快捷键的新操作由默认值补齐。
+fn migrate_v3_to_v4(settings: &mut Map<String, Value>) -> Result<()> {
+    let Some(Value::Object(legacy)) = settings.remove("hotkeys") else {
+        return Ok(());
+    };
+
+    let mut bindings = match serde_json::to_value(HotkeyConfig::default())? {
+        Value::Object(mut config) => match config.remove("bindings") {
+            Some(Value::Object(bindings)) => bindings,
+            _ => Map::new(),
+        },
+        _ => Map::new(),
+    };
+
+    for (field, _, action) in LEGACY_HOTKEYS {
+        let Some(shortcut) = legacy.get(field).and_then(Value::as_str) else {
+            continue;
+        };
+        let scope = match action {
+            AppAction::ToggleAutoSwitch => HotkeyScope::Window,
+            _ => HotkeyScope::Global,
+        };
+        bindings.insert(
+            action.id().to_string(),
+            serde_json::json!({ "shortcut": shortcut, "scope": scope }),
+        );
+    }
+
+    let mut hotkeys = Map::new();
+    hotkeys.insert("bindings".to_string(), Value::Object(bindings));
+    settings.insert("hotkeys".to_string(), Value::Object(hotkeys));
+    Ok(())
+}
+
+fn normalize_choice(settings: &mut Map<String, Value>, section: &str, field: &str, allowed: &[&str], default: &str) {
+    let Some(Value::Object(section_map)) = settings.get_mut(section) else {
+        return;
+    };
+    let Some(value) = section_map.get_mut(field) else {
+        return;
+    };
+
+    let normalized = value
+        .as_str()
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| allowed.contains(&s.as_str()));
+
+    match normalized {
+        Some(choice) => *value = Value::String(choice),
+        None => {
+            log::warn!("⚠️ 配置项 {}.{} 的值 {} 无效，已重置为 {}", section, field, value, default);
+            *value = Value::String(default.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DifficultyPreference, RatioPreset, ReviewMode, ThemeMode};
+
+    #[test]
+    fn current_settings_are_not_migrated() {
+        let content = serde_json::to_string(&Settings::default()).unwrap();
+        let loaded = parse_settings(&content).unwrap();
+        assert!(!loaded.migrated);
+        assert_eq!(loaded.settings, Settings::default());
+    }
+
+    #[test]
+    fn unversioned_settings_migrate_to_current_version() {
+        let loaded = parse_settings(r#"{ "auto_switch": false, "switch_interval": 15 }"#).unwrap();
+        assert!(loaded.migrated);
+        assert_eq!(loaded.settings.version, CURRENT_SETTINGS_VERSION);
+        assert!(!loaded.settings.auto_switch);
+        assert_eq!(loaded.settings.switch_interval, 15);
+    }
+
+    #[test]
+    fn newer_version_is_rejected() {
+        let content = format!(r#"{{ "version": {} }}"#, CURRENT_SETTINGS_VERSION + 1);
+        assert!(parse_settings(&content).is_err());
+    }
+
+    #[test]
+    fn v1_choices_are_normalized() {
+        let loaded = parse_settings(
+            r#"{
+                "version": 1,
+                "window_size": { "width": 400, "height": 400, "ratio_preset": "Square" },
+                "theme": { "mode": " DARK " },
+                "learning": { "review_mode": "shuffle", "difficulty_preference": "Hard" }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(loaded.settings.window_size.ratio_preset, RatioPreset::Square);
+        assert_eq!(loaded.settings.theme.mode, ThemeMode::Dark);
+        assert_eq!(loaded.settings.learning.review_mode, ReviewMode::Smart);
+        assert_eq!(loaded.settings.learning.difficulty_preference, DifficultyPreference::Hard);
+    }
+
+    #[test]
+    fn v2_unsafe_hotkeys_are_reset() {
+        let mut settings = serde_json::json!({
+            "hotkeys": { "play_pronunciation": "Space", "next_word": "Alt+N", "toggle_window": "" }
+        });
+        let Value::Object(object) = &mut settings else { unreachable!() };
+        migrate_v2_to_v3(object).unwrap();
+
+        assert_eq!(settings["hotkeys"]["play_pronunciation"], "CmdOrCtrl+Shift+Space");
+        assert_eq!(settings["hotkeys"]["next_word"], "Alt+N");
+        assert_eq!(settings["hotkeys"]["toggle_window"], "");
+    }
+
+    #[test]
+    fn v3_hotkeys_become_action_bindings() {
+        let loaded = parse_settings(
+            r#"{
+                "version": 3,
+                "hotkeys": {
+                    "toggle_window": "Alt+W",
+                    "next_word": "",
+                    "prev_word": "CmdOrCtrl+Left",
+                    "play_pronunciation": "CmdOrCtrl+Shift+Space",
+                    "pause_learning": "CmdOrCtrl+P"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let bindings = &loaded.settings.hotkeys.bindings;
+        let toggle_window = &bindings[&AppAction::ToggleWindow];
+        assert_eq!(toggle_window.shortcut, "Alt+W");
+        assert_eq!(toggle_window.scope, HotkeyScope::Global);
+        // 清空的快捷键保持清空，不被默认值补上
+        assert_eq!(bindings[&AppAction::NextWord].shortcut, "");
+        assert_eq!(bindings[&AppAction::ToggleAutoSwitch].scope, HotkeyScope::Window);
+        // v3 没有的操作使用默认绑定
+        assert_eq!(bindings[&AppAction::RevealAnswer], HotkeyConfig::default().bindings[&AppAction::RevealAnswer]);
+    }
+}
@@ -0,0 +1,42 @@
+use crate::utils::atomic_file;
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Wry};
+use tauri_plugin_store::{resolve_store_path, Store, StoreExt};
+
+// 串行化保存，避免较早读取的内容后写入而覆盖较新的内容
+static SAVE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// 打开 tauri-plugin-store 的 store（学习进度、统计、会话、成就等）
+///
+/// 插件自带的保存直接覆盖写入文件，因此这里关闭自动保存，修改后通过 [`save`] 原子写入。
+/// 首次打开时文件损坏会先从 `.bak` 恢复，否则插件会把损坏的文件当作空 store 加载，
+/// 下次保存时覆盖掉原有数据。
+pub fn open(app: &AppHandle, name: &str) -> Result<Arc<Store<Wry>>> {
+    if app.get_store(name).is_none() {
+        let path = resolve_store_path(app, name)?;
+        if path.exists() || atomic_file::backup_path(&path).exists() {
+            if let Err(e) = atomic_file::read_with_recovery(&path, parse) {
+                log::error!("❌ 无法读取 store 文件 {}: {}", name, e);
+            }
+        }
+    }
+
+    Ok(app.store_builder(name).disable_auto_save().build()?)
+}
+
+/// 把 store 当前的内容原子写入文件，旧版本保留为 `.bak`
+pub fn save(app: &AppHandle, name: &str) -> Result<()> {
+    let _guard = SAVE_LOCK.lock().unwrap();
+
+    let store = open(app, name)?;
+    let entries: serde_json::Map<String, serde_json::Value> = store.entries().into_iter().collect();
+    let path = resolve_store_path(app, name)?;
+    atomic_file::write_atomic(&path, serde_json::to_vec_pretty(&entries)?)
+}
+
+fn parse(content: &str) -> Result<()> {
+    serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(content)?;
+    Ok(())
+}
@@ -0,0 +1,139 @@
+import { onMounted, onUnmounted } from 'vue'
+import { invoke } from '@tauri-apps/api/core'
+import { listen, type UnlistenFn } from '@tauri-apps/api/event'
+import type { AppAction } from '../types/generated/AppAction'
+import type { HotkeyScope } from '../types/generated/HotkeyScope'
+
+export interface ActionBinding {
+  action: AppAction
+  label: string
+  shortcut: string | null
+  scope: HotkeyScope
+}
+
+export type ActionHandlers = Partial<Record<AppAction, () => void>>
+
+// 监听后端分发的操作（来自全局快捷键、菜单、托盘或窗口内快捷键）
+export function useActionHandlers(handlers: ActionHandlers) {
+  let unlisten: UnlistenFn | null = null
+
+  onMounted(async () => {
+    unlisten = await listen<AppAction>('action-triggered', (event) => {
+      const handler = handlers[event.payload]
+      if (handler) {
+        console.log(`🎬 执行操作: ${event.payload}`)
+        handler()
+      }
+    })
+  })
+
+  onUnmounted(() => {
+    if (unlisten) {
+      unlisten()
+    }
+  })
+}
+
+// 窗口内快捷键：按后端配置匹配按键，统一交给后端分发
+export function useWindowBindings() {
+  let bindings: ActionBinding[] = []
+  let unlistenSettings: UnlistenFn | null = null
+
+  const loadBindings = async () => {
+    try {
+      const all = await invoke<ActionBinding[]>('get_action_bindings')
+      bindings = all.filter(b => b.scope === 'window' && b.shortcut)
+    } catch (error) {
+      console.error('❌ 加载窗口快捷键失败:', error)
+    }
+  }
+
+  const handleKeydown = (event: KeyboardEvent) => {
+    // 输入框中只响应带 Ctrl/Alt/Cmd 的组合键，避免影响输入
+    const target = event.target as HTMLElement | null
+    const isEditing = !!target && (target.tagName === 'INPUT' || target.tagName === 'TEXTAREA' || target.isContentEditable)
+    if (isEditing && !event.ctrlKey && !event.altKey && !event.metaKey) {
+      return
+    }
+
+    const binding = bindings.find(b => matchesShortcut(event, b.shortcut!))
+    if (binding) {
+      event.preventDefault()
+      invoke('trigger_action', { action: binding.action }).catch(error => {
+        console.error(`❌ 触发操作失败: ${binding.action}`, error)
+      })
+    }
+  }
+
+  onMounted(async () => {
+    await loadBindings()
+    window.addEventListener('keydown', handleKeydown)
+    unlistenSettings = await listen('settings-changed', loadBindings)
+  })
+
+  onUnmounted(() => {
+    window.removeEventListener('keydown', handleKeydown)
+    if (unlistenSettings) {
+      unlistenSettings()
+    }
+  })
+}
+
+const isMac = navigator.platform.toUpperCase().includes('MAC')
+
+// 把快捷键主键转换为 KeyboardEvent.code
+const toKeyCode = (key: string): string => {
+  const upper = key.toUpperCase()
+  if (/^[A-Z]$/.test(upper)) return `Key${upper}`
+  if (/^[0-9]$/.test(upper)) return `Digit${upper}`
+  switch (upper) {
+    case 'UP': return 'ArrowUp'
+    case 'DOWN': return 'ArrowDown'
+    case 'LEFT': return 'ArrowLeft'
+    case 'RIGHT': return 'ArrowRight'
+    case 'ESC': return 'Escape'
+    default: return key
+  }
+}
+
+const matchesShortcut = (event: KeyboardEvent, shortcut: string): boolean => {
+  const parts = shortcut.split('+').map(p => p.trim())
+  const key = parts.pop()
+  if (!key) return false
+
+  let ctrl = false
+  let meta = false
+  let alt = false
+  let shift = false
+  for (const modifier of parts) {
+    switch (modifier.toLowerCase()) {
+      case 'cmdorctrl':
+      case 'commandorcontrol':
+        if (isMac) meta = true
+        else ctrl = true
+        break
+      case 'ctrl':
+      case 'control':
+        ctrl = true
+        break
+      case 'cmd':
+      case 'command':
+      case 'super':
+        meta = true
+        break
+      case 'alt':
+      case 'option':
+        alt = true
+        break
+      case 'shift':
+        shift = true
+        break
+    }
+  }
+
+  if (event.ctrlKey !== ctrl || event.metaKey !== meta || event.altKey !== alt || event.shiftKey !== shift) {
+    return false
+  }
+
+  return event.code.toLowerCase() === toKeyCode(key).toLowerCase()
+}
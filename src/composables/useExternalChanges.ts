@@ -0,0 +1,69 @@
+import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
+import { ElMessage, ElMessageBox } from 'element-plus'
+import type { FieldError } from '../types/settings'
+
+// 后端 services::file_watcher 发送的外部修改事件
+type WatchedFile = 'settings' | 'wordbook'
+
+interface ExternalChangeEvent {
+  file: WatchedFile
+  errors: FieldError[] // 仅在修改被拒绝时非空
+}
+
+const FILE_NAMES: Record<WatchedFile, string> = {
+  settings: '设置文件',
+  wordbook: '单词本文件'
+}
+
+let initialized = false
+
+// 处理设置文件和单词本文件的外部修改
+// 单词本重新加载后后端会发送 wordbook-changed，由单词页面重新加载单词列表
+export async function initializeExternalChanges() {
+  if (initialized) return
+  initialized = true
+
+  await listen<ExternalChangeEvent>('external-change-applied', (event) => {
+    ElMessage.info(`📝 ${FILE_NAMES[event.payload.file]}已在外部修改，已重新加载`)
+  })
+
+  await listen<ExternalChangeEvent>('external-change-rejected', (event) => {
+    const { file, errors } = event.payload
+    const detail = errors.map(e => (e.field ? `${e.field}: ${e.message}` : e.message)).join('\n')
+    ElMessage({
+      message: `⚠️ ${FILE_NAMES[file]}的外部修改无效，已恢复原来的内容（修改后的文件已另存）\n${detail}`,
+      type: 'warning',
+      duration: 6000,
+      showClose: true
+    })
+  })
+
+  // 存在未保存的修改时由用户决定保留哪一份
+  await listen<ExternalChangeEvent>('external-change-conflict', async (event) => {
+    const file = event.payload.file
+    let accept: boolean
+    try {
+      await ElMessageBox.confirm(
+        `${FILE_NAMES[file]}已在外部修改，而设置页面中还有未保存的修改。是否加载外部修改？`,
+        '检测到外部修改',
+        {
+          confirmButtonText: '加载外部修改',
+          cancelButtonText: '保留程序中的数据',
+          type: 'warning',
+          distinguishCancelAndClose: true
+        }
+      )
+      accept = true
+    } catch {
+      accept = false
+    }
+
+    try {
+      await invoke('resolve_external_change', { file, accept })
+    } catch (error) {
+      console.error('❌ 处理外部修改失败:', error)
+      ElMessage.error(`处理外部修改失败: ${error}`)
+    }
+  })
+}
@@ -4,8 +4,6 @@ import { ElMessage } from 'element-plus'
 interface ReminderSettings {
   enabled: boolean
   interval: number // 分钟
-  title: string
-  message: string
   playSound: boolean
   showBadge: boolean
   dailyGoal: number
@@ -17,8 +15,6 @@ interface ReminderSettings {
 const defaultReminderSettings: ReminderSettings = {
   enabled: false,
   interval: 30, // 30分钟提醒一次
-  title: '🎯 该学习单词啦！',
-  message: '距离今天的学习目标还有进度，继续加油！',
   playSound: true,
   showBadge: true,
   dailyGoal: 20,
@@ -29,7 +25,7 @@ const defaultReminderSettings: ReminderSettings = {
 
 const reminderSettings = ref<ReminderSettings>({ ...defaultReminderSettings })
 const permissionGranted = ref<boolean>(false)
-const reminderTimerId = ref<number | null>(null)
+const reminderActive = ref<boolean>(false)
 const studiedToday = ref<number>(0)
 
 export function useNotifications() {
@@ -95,22 +91,10 @@ export function useNotifications() {
     }
   }
 
-  // 发送学习提醒
+  // 立即发送一次学习提醒，内容由后端按今日进度和待复习单词生成
   const sendStudyReminder = async () => {
-    const remaining = Math.max(0, reminderSettings.value.dailyGoal - studiedToday.value)
-    const progress = Math.round((studiedToday.value / reminderSettings.value.dailyGoal) * 100)
-    
-    let message = reminderSettings.value.message
-    if (remaining > 0) {
-      message = `今天还需要学习 ${remaining} 个单词，已完成 ${progress}%`
-    } else {
-      message = `🎉 恭喜！今天的学习目标已完成！已学习 ${studiedToday.value} 个单词`
-    }
-
-    await sendNotification(
-      reminderSettings.value.title,
-      message
-    )
+    const { invoke } = await import('@tauri-apps/api/core')
+    await invoke('send_study_reminder')
   }
 
   // 检查是否在学习时间内
@@ -134,6 +118,27 @@ export function useNotifications() {
     return currentTime >= startTime && currentTime <= endTime
   }
 
+  // 提醒由后端定时发送（窗口隐藏时同样有效），这里把提醒设置写入后端的 notification 设置
+  const syncBackendReminder = async (enabled: boolean) => {
+    const { invoke } = await import('@tauri-apps/api/core')
+    const settings = reminderSettings.value
+    await invoke('patch_settings', {
+      patch: {
+        notification: {
+          study_reminders: enabled,
+          reminder_interval: settings.interval,
+          sound_enabled: settings.playSound,
+          // 学习时间以外的时段即免打扰时段
+          quiet_hours: { enabled: true, start: settings.studyEndTime, end: settings.studyStartTime },
+          reminder_weekdays: settings.weekdays
+            .map((selected, index) => selected ? index : -1)
+            .filter(index => index !== -1)
+        }
+      }
+    })
+    reminderActive.value = enabled
+  }
+
   // 启动定时提醒
   const startReminder = async () => {
     if (!reminderSettings.value.enabled) {
@@ -147,30 +152,32 @@ export function useNotifications() {
       }
     }
 
-    // 清除现有定时器
-    stopReminder()
-
-    // 设置新的定时器
-    const intervalMs = reminderSettings.value.interval * 60 * 1000
-    reminderTimerId.value = window.setInterval(() => {
-      if (isStudyTime()) {
-        sendStudyReminder()
-      }
-    }, intervalMs)
-
-    console.log(`✅ 学习提醒已启动，间隔 ${reminderSettings.value.interval} 分钟`)
-    ElMessage.success(`📢 学习提醒已启动！每 ${reminderSettings.value.interval} 分钟提醒一次`)
+    try {
+      await syncBackendReminder(true)
+      console.log(`✅ 学习提醒已启动，间隔 ${reminderSettings.value.interval} 分钟`)
+      ElMessage.success(`📢 学习提醒已启动！每 ${reminderSettings.value.interval} 分钟提醒一次`)
+    } catch (error) {
+      console.error('启动学习提醒失败:', error)
+      ElMessage.error('❌ 启动学习提醒失败')
+    }
   }
 
   // 停止定时提醒
-  const stopReminder = () => {
-    if (reminderTimerId.value) {
-      clearInterval(reminderTimerId.value)
-      reminderTimerId.value = null
+  const stopReminder = async () => {
+    try {
+      await syncBackendReminder(false)
       console.log('⏹️ 学习提醒已停止')
+    } catch (error) {
+      console.error('停止学习提醒失败:', error)
     }
   }
 
+  // 临时免打扰，minutes 为 null 时取消
+  const setDoNotDisturb = async (minutes: number | null) => {
+    const { invoke } = await import('@tauri-apps/api/core')
+    await invoke('set_do_not_disturb', { minutes })
+  }
+
   // 更新提醒设置
   const updateReminderSettings = (newSettings: Partial<ReminderSettings>) => {
     reminderSettings.value = { ...reminderSettings.value, ...newSettings }
@@ -231,7 +238,7 @@ export function useNotifications() {
   }
 
   // 计算属性
-  const isReminderActive = computed(() => !!reminderTimerId.value)
+  const isReminderActive = computed(() => reminderActive.value)
   const progressPercentage = computed(() => 
     Math.round((studiedToday.value / reminderSettings.value.dailyGoal) * 100)
   )
@@ -250,8 +257,14 @@ export function useNotifications() {
       await requestPermission()
     }
     
-    if (reminderSettings.value.enabled) {
-      await startReminder()
+    // 提醒是否开启以后端设置为准
+    try {
+      const { invoke } = await import('@tauri-apps/api/core')
+      const state = await invoke<{ enabled: boolean }>('get_reminder_state')
+      reminderActive.value = state.enabled
+      reminderSettings.value.enabled = state.enabled
+    } catch (error) {
+      console.error('获取学习提醒状态失败:', error)
     }
   }
 
@@ -273,6 +286,7 @@ export function useNotifications() {
     sendStudyStartReminder,
     startReminder,
     stopReminder,
+    setDoNotDisturb,
     updateReminderSettings,
     updateStudyProgress,
     initializeNotifications,
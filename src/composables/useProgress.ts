@@ -1,31 +1,20 @@
 import { ref, computed } from 'vue'
 import { Store } from '@tauri-apps/plugin-store'
 import { invoke } from '@tauri-apps/api/core'
-import type { WordProgress, LearningStats, StudySession } from '../types/word'
+import type { WordProgress, LearningStats, StudySession, SessionType } from '../types/word'
 
 // Store 实例
 let progressStore: Store | null = null
-let statsStore: Store | null = null
-let sessionsStore: Store | null = null
 
 // 状态
 const currentStats = ref<LearningStats | null>(null)
 const isLoading = ref(false)
-const studySessionData = ref<StudySession>({
-  words_studied: [],
-  session_duration: 0,
-  correct_answers: 0,
-  total_answers: 0,
-  average_time_per_word: 0,
-  session_type: 'mixed'
-})
+const studySessionData = ref<StudySession | null>(null)
 
 // 初始化 Store
 export async function initializeProgressStores() {
   if (!progressStore) {
     progressStore = await Store.load('user_progress.json')
-    statsStore = await Store.load('learning_stats.json')
-    sessionsStore = await Store.load('study_sessions.json')
   }
 }
 
@@ -50,30 +39,19 @@ export function useProgress() {
     wordId: string,
     progress: number,
     masteryLevel: number,
-    isCorrect: boolean,
-    timeSpent: number = 30
+    isCorrect: boolean
   ): Promise<boolean> => {
     try {
       isLoading.value = true
       
-      // 调用后端API更新进度
+      // 调用后端API更新进度，用时由后端从单词显示时开始计算
       await invoke('update_word_progress', {
         wordId,
         progress,
         masteryLevel,
-        isCorrect,
-        timeSpent
+        isCorrect
       })
 
-      // 更新学习会话数据
-      if (!studySessionData.value.words_studied.includes(wordId)) {
-        studySessionData.value.words_studied.push(wordId)
-      }
-      studySessionData.value.total_answers++
-      if (isCorrect) {
-        studySessionData.value.correct_answers++
-      }
-      
       console.log('✅ 单词学习进度已保存:', { wordId, progress, masteryLevel })
       return true
     } catch (error) {
@@ -101,48 +79,41 @@ export function useProgress() {
     }
   }
 
-  // 开始新的学习会话
-  const startStudySession = (sessionType: 'review' | 'new_words' | 'mixed' = 'mixed') => {
-    studySessionData.value = {
-      words_studied: [],
-      session_duration: 0,
-      correct_answers: 0,
-      total_answers: 0,
-      average_time_per_word: 0,
-      session_type: sessionType
-    }
-    
-    // 记录会话开始时间
-    const startTime = Date.now()
+  // 开始新的学习会话，之后的评分由后端记入该会话
+  const startStudySession = async (sessionType: SessionType = 'mixed') => {
+    const session = await invoke<StudySession>('start_study_session', { sessionType })
+    studySessionData.value = session
+    const sessionId = session.session_id
     
     return {
-      startTime,
-      endSession: async () => {
-        const endTime = Date.now()
-        studySessionData.value.session_duration = Math.round((endTime - startTime) / 1000)
-        
-        if (studySessionData.value.words_studied.length > 0) {
-          studySessionData.value.average_time_per_word = 
-            studySessionData.value.session_duration / studySessionData.value.words_studied.length
+      sessionId,
+      pause: async () => {
+        studySessionData.value = await invoke<StudySession>('pause_study_session', { sessionId })
+      },
+      resume: async () => {
+        studySessionData.value = await invoke<StudySession>('resume_study_session', { sessionId })
+      },
+      endSession: async (): Promise<StudySession | null> => {
+        try {
+          const ended = await invoke<StudySession>('end_study_session', { sessionId })
+          studySessionData.value = null
+          console.log('✅ 学习会话已保存:', ended.session_id)
+          return ended
+        } catch (error) {
+          console.error('❌ 结束学习会话失败:', error)
+          return null
         }
-        
-        await saveStudySession()
       }
     }
   }
 
-  // 保存学习会话
-  const saveStudySession = async (): Promise<boolean> => {
+  // 按日期查询学习会话（YYYY-MM-DD，两端都包含）
+  const getStudySessions = async (from?: string, to?: string): Promise<StudySession[]> => {
     try {
-      await invoke('save_study_session', {
-        sessionData: studySessionData.value
-      })
-      
-      console.log('✅ 学习会话已保存')
-      return true
+      return await invoke<StudySession[]>('get_study_sessions', { from: from ?? null, to: to ?? null })
     } catch (error) {
-      console.error('❌ 保存学习会话失败:', error)
-      return false
+      console.error('❌ 获取学习会话失败:', error)
+      return []
     }
   }
 
@@ -185,9 +156,8 @@ export function useProgress() {
   // 设置每日学习目标
   const setDailyGoal = async (goal: number): Promise<boolean> => {
     try {
-      await initializeProgressStores()
-      await statsStore?.set('daily_goal', goal)
-      await statsStore?.save()
+      // 每日目标保存在设置中，学习数据的 store 只由后端写入
+      await invoke('patch_settings', { patch: { learning: { daily_goal: goal } } })
       
       // 更新当前统计数据
       if (currentStats.value) {
@@ -260,7 +230,7 @@ export function useProgress() {
     updateWordProgress,
     getLearningStats,
     startStudySession,
-    saveStudySession,
+    getStudySessions,
     exportProgress,
     importProgress,
     setDailyGoal,
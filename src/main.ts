@@ -8,6 +8,7 @@ import App from './App.vue'
 import router from './router'
 import { useSettingsStore } from './stores/settingsStore'
 import { useTheme } from './composables/useTheme'
+import { initializeExternalChanges } from './composables/useExternalChanges'
 import './styles/win11.scss'
 import './styles/theme.scss'
 
@@ -22,8 +23,12 @@ app.use(ElementPlus)
 
 // 初始化设置存储
 const settingsStore = useSettingsStore()
+settingsStore.loadPreferences()
 settingsStore.loadSettings()
 
+// 设置文件和单词本文件的外部修改
+initializeExternalChanges()
+
 // 初始化主题系统
 const { initializeTheme } = useTheme()
 initializeTheme()
@@ -1,102 +1,136 @@
 import { defineStore } from 'pinia'
-import { ref } from 'vue'
-import type { Settings } from '../types/settings'
+import { computed, ref, watch } from 'vue'
+import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
+import type { FieldError, LocalPreferences, Settings } from '../types/settings'
+
+const PREFERENCES_KEY = 'word-pony-preferences'
+
+// 后端设置变更事件内容（services::settings::SettingsChange）
+interface SettingsChange {
+  sections: string[]
+  old: Settings
+  new: Settings
+}
 
 export const useSettingsStore = defineStore('settings', () => {
-  // 默认设置
-  const defaultSettings: Settings = {
-    autoSwitch: false,
-    switchInterval: 7,
-    alwaysOnTop: false,
-    showInTray: true,
-    enableTTS: true,
-    enableHotkeys: false,    // 默认禁用快捷键
-    autoCheckUpdates: false, // 默认禁用自动更新检查
-    windowSize: {
-      width: 400,
-      height: 600,
-      aspectRatio: '9:16'
-    },
-    hotkeys: {
-      nextWord: '',           // 默认无快捷键配置
-      previousWord: '',
-      playPronunciation: '',
-      togglePause: '',
-      toggleWindow: '',
-      markMastered: '',
-      showSettings: ''
-    },
-    theme: {
-      mode: 'auto',
-      primaryColor: '#667eea',
-      backgroundColor: '#ffffff'
-    }
+  // 前端偏好的默认值
+  const defaultPreferences: LocalPreferences = {
+    autoCheckUpdates: false // 默认禁用自动更新检查
   }
 
-  // 状态
-  const settings = ref<Settings>({ ...defaultSettings })
+  // 状态：后端设置加载完成前为 null
+  const settings = ref<Settings | null>(null)
+  const preferences = ref<LocalPreferences>(structuredClone(defaultPreferences))
+  // 最近一次与后端一致的设置，设置页面中的修改在保存前与它比较
+  const savedSettings = ref<string | null>(null)
+
+  const hasUnsavedChanges = computed(() =>
+    settings.value !== null && JSON.stringify(settings.value) !== savedSettings.value
+  )
+
+  // 存在未保存的修改时，后端检测到设置文件的外部修改会先询问用户
+  watch(hasUnsavedChanges, async (unsaved) => {
+    try {
+      await invoke('set_unsaved_changes', { unsaved })
+    } catch (error) {
+      console.error('❌ 同步未保存状态失败:', error)
+    }
+  })
+
+  const applySettings = (value: Settings) => {
+    settings.value = value
+    savedSettings.value = JSON.stringify(value)
+  }
 
   // 方法
-  const updateSettings = (newSettings: Partial<Settings>) => {
-    console.log('🔄 更新设置:', newSettings)
-    console.log('🔄 更新前settings:', JSON.stringify(settings.value, null, 2))
-    
-    // 深度合并，保持响应式
-    Object.keys(newSettings).forEach(key => {
-      const newValue = newSettings[key as keyof Settings]
-      if (newValue !== undefined) {
-        if (typeof newValue === 'object' && newValue !== null && !Array.isArray(newValue)) {
-          // 对象类型，深度合并
-          if (settings.value[key as keyof Settings] && typeof settings.value[key as keyof Settings] === 'object') {
-            Object.assign(settings.value[key as keyof Settings], newValue)
-          } else {
-            (settings.value as any)[key] = { ...newValue }
-          }
-        } else {
-          // 基础类型，直接赋值
-          (settings.value as any)[key] = newValue
-        }
-      }
+  const loadSettings = async () => {
+    try {
+      applySettings(await invoke<Settings>('get_settings'))
+      console.log('✅ 设置已加载')
+    } catch (error) {
+      console.error('❌ 设置加载失败:', error)
+    }
+
+    // 托盘、快捷键或外部编辑修改设置后同步到前端
+    await listen<SettingsChange>('settings-changed', event => {
+      applySettings(event.payload.new)
     })
-    
-    console.log('🔄 更新后settings:', JSON.stringify(settings.value, null, 2))
-    saveSettings()
   }
 
-  const saveSettings = () => {
+  // 以 JSON Merge Patch 语义部分更新后端设置，字段无效时抛出 FieldError[]
+  const patchSettings = async (patch: Record<string, unknown>) => {
+    console.log('🔄 更新设置:', patch)
+    applySettings(await invoke<Settings>('patch_settings', { patch }))
+  }
+
+  // 保存设置页面中直接编辑过的完整设置
+  const saveSettings = async () => {
+    if (!settings.value) return
     try {
-      localStorage.setItem('word-pony-settings', JSON.stringify(settings.value))
+      await invoke('update_settings', { settings: settings.value })
+      savedSettings.value = JSON.stringify(settings.value)
       console.log('✅ 设置已保存')
+    } catch (errors) {
+      console.error('❌ 设置保存失败:', errors)
+      throw errors as FieldError[]
+    }
+  }
+
+  // 放弃设置页面中未保存的修改
+  const discardChanges = () => {
+    if (savedSettings.value) {
+      settings.value = JSON.parse(savedSettings.value)
+    }
+  }
+
+  const updatePreferences = (changes: Partial<LocalPreferences>) => {
+    Object.assign(preferences.value, changes)
+    savePreferences()
+  }
+
+  const savePreferences = () => {
+    try {
+      localStorage.setItem(PREFERENCES_KEY, JSON.stringify(preferences.value))
     } catch (error) {
-      console.error('❌ 设置保存失败:', error)
+      console.error('❌ 偏好保存失败:', error)
     }
   }
 
-  const loadSettings = () => {
+  const loadPreferences = () => {
     try {
-      const savedSettings = localStorage.getItem('word-pony-settings')
-      if (savedSettings) {
-        settings.value = { ...defaultSettings, ...JSON.parse(savedSettings) }
-        console.log('✅ 设置已加载')
+      const saved = localStorage.getItem(PREFERENCES_KEY)
+      if (saved) {
+        // 只保留仍在使用的字段，旧版本保存的快捷键已改由后端设置管理
+        const parsed = JSON.parse(saved)
+        preferences.value = {
+          autoCheckUpdates: parsed.autoCheckUpdates ?? defaultPreferences.autoCheckUpdates
+        }
       }
     } catch (error) {
-      console.error('❌ 设置加载失败:', error)
-      settings.value = { ...defaultSettings }
+      console.error('❌ 偏好加载失败:', error)
+      preferences.value = structuredClone(defaultPreferences)
     }
   }
 
-  const resetSettings = () => {
-    settings.value = { ...defaultSettings }
-    saveSettings()
+  const resetPreferences = () => {
+    preferences.value = structuredClone(defaultPreferences)
+    savePreferences()
   }
 
   return {
     // 状态
     settings,
+    preferences,
+    hasUnsavedChanges,
     // 方法
-    updateSettings,
-    saveSettings,
     loadSettings,
-    resetSettings
+    patchSettings,
+    saveSettings,
+    discardChanges,
+    updatePreferences,
+    savePreferences,
+    loadPreferences,
+    resetPreferences
   }
-}) 
\ No newline at end of file
+})
@@ -1,6 +1,6 @@
 import { defineStore } from 'pinia'
 import { ref, computed } from 'vue'
-import type { Word } from '../types/word'
+import type { Word, WordProgress, SessionType } from '../types/word'
 import { useProgress } from '../composables/useProgress'
 
 // 改进的Tauri环境检测函数
@@ -43,7 +43,7 @@ export const useWordStore = defineStore('word', () => {
   } = useProgress()
   
   // 学习会话管理
-  let currentSession: { startTime: number; endSession: () => Promise<void> } | null = null
+  let currentSession: Awaited<ReturnType<typeof startStudySession>> | null = null
 
   // 计算属性
   const currentWord = computed(() => wordList.value[currentWordIndex.value])
@@ -222,8 +222,7 @@ export const useWordStore = defineStore('word', () => {
         wordId, 
         newProgress, 
         masteryLevel, 
-        isCorrect,
-        30 // 假设每次学习30秒
+        isCorrect
       )
       
       if (success) {
@@ -235,18 +234,35 @@ export const useWordStore = defineStore('word', () => {
   }
 
   // 开始学习会话
-  const beginStudySession = async (sessionType: 'review' | 'new_words' | 'mixed' = 'mixed') => {
+  const beginStudySession = async (sessionType: SessionType = 'mixed') => {
     if (currentSession) {
       console.warn('⚠️ 已有活跃的学习会话，将结束当前会话')
       await currentSession.endSession()
     }
     
-    currentSession = startStudySession(sessionType)
+    currentSession = await startStudySession(sessionType)
     console.log('🎯 学习会话已开始:', sessionType)
     
     return currentSession
   }
 
+  // 窗口隐藏时暂停学习会话，暂停时间不计入会话时长
+  const pauseStudySession = async () => {
+    try {
+      await currentSession?.pause()
+    } catch (error) {
+      console.error('❌ 暂停学习会话失败:', error)
+    }
+  }
+
+  const resumeStudySession = async () => {
+    try {
+      await currentSession?.resume()
+    } catch (error) {
+      console.error('❌ 继续学习会话失败:', error)
+    }
+  }
+
   // 结束学习会话
   const endStudySession = async () => {
     if (currentSession) {
@@ -307,6 +323,17 @@ export const useWordStore = defineStore('word', () => {
     await getLearningStats()
   }
 
+  // 同步后端保存的学习进度（例如托盘快速评分）
+  const applyWordProgress = (progress: WordProgress) => {
+    const word = wordList.value.find(w => w.id === progress.word_id)
+    if (word) {
+      word.masteryLevel = progress.mastery_level
+      word.progress = progress.progress
+      word.reviewCount = progress.review_count
+      word.lastReview = new Date(progress.last_review)
+    }
+  }
+
   // 根据ID设置当前单词
   const setCurrentWordById = (wordId: string): boolean => {
     const index = wordList.value.findIndex(word => word.id === wordId)
@@ -340,10 +367,13 @@ export const useWordStore = defineStore('word', () => {
     
     // 新增：学习进度相关方法
     beginStudySession,
+    pauseStudySession,
+    resumeStudySession,
     endStudySession,
     markWordKnown,
     markWordUnknown,
     refreshLearningStats,
+    applyWordProgress,
     setCurrentWordById
   }
 }) 
\ No newline at end of file
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AchievementCategory } from "./AchievementCategory";
+
+/**
+ * 一个成就及其解锁状态；标签成就按标签动态生成，id 为 `tag:<标签名>`
+ */
+export type Achievement = { id: string, category: AchievementCategory, title: string, description: string, 
+/**
+ * None 表示尚未解锁
+ */
+unlocked_at: string | null, };
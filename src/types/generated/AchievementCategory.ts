@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 成就的类别，设置界面按类别分组显示
+ */
+export type AchievementCategory = "streak" | "mastery" | "session" | "tag" | "schedule";
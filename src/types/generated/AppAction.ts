@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type AppAction = "toggle_window" | "next_word" | "prev_word" | "reveal_answer" | "grade_1" | "grade_2" | "grade_3" | "grade_4" | "mark_known" | "speak_word" | "speak_example" | "toggle_auto_switch" | "open_search" | "toggle_always_on_top" | "study_now";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 日历热力图中的一天（本地日期）
+ */
+export type DailyReviewCount = { date: string, reviews: number, correct: number, };
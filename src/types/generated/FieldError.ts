@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 设置校验失败时返回给前端的字段级错误
+ */
+export type FieldError = { 
+/**
+ * 以点分隔的字段路径，如 "theme.mode"
+ */
+field: string, message: string, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 答错最多的单词
+ */
+export type HardWord = { word_id: string, 
+/**
+ * 单词已从单词本中删除时为空
+ */
+word: string, reviews: number, incorrect: number, accuracy: number, average_time_spent: number, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HotkeyScope } from "./HotkeyScope";
+
+export type HotkeyBinding = { 
+/**
+ * 空字符串表示不绑定
+ */
+shortcut: string, scope: HotkeyScope, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AppAction } from "./AppAction";
+import type { HotkeyBinding } from "./HotkeyBinding";
+
+export type HotkeyConfig = { 
+/**
+ * 未出现的操作没有绑定快捷键
+ */
+bindings: { [key in AppAction]?: HotkeyBinding }, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 一天中某个小时（本地时间）的答题情况
+ */
+export type HourlyPerformance = { hour: number, reviews: number, correct: number, accuracy: number, average_time_spent: number, };
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DifficultyPreference } from "./DifficultyPreference";
+import type { ReviewMode } from "./ReviewMode";
+
+export type LearningConfig = { daily_goal: number, auto_next: boolean, show_pronunciation: boolean, show_examples: boolean, review_mode: ReviewMode, difficulty_preference: DifficultyPreference, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { QuietHours } from "./QuietHours";
+
+export type NotificationConfig = { enable_notifications: boolean, study_reminders: boolean, 
+/**
+ * 分钟
+ */
+reminder_interval: number, achievement_notifications: boolean, sound_enabled: boolean, quiet_hours: QuietHours, 
+/**
+ * 发送提醒的星期，0 为周日
+ */
+reminder_weekdays: Array<number>, 
+/**
+ * 临时免打扰，到期后自动恢复
+ */
+do_not_disturb_until: string | null, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PausePolicy = { when_idle: boolean, 
+/**
+ * 无键鼠操作超过该时长视为离开
+ */
+idle_seconds: number, 
+/**
+ * 全屏程序（游戏、演示）运行时暂停
+ */
+when_fullscreen: boolean, 
+/**
+ * 朗读未结束时不计时，读完再切换
+ */
+while_speaking: boolean, };
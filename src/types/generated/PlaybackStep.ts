@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PlaybackStep = { "type": "word", voice_id: string, } | { "type": "example", index: number, voice_id: string, } | { "type": "translation", voice_id: string, } | { "type": "example_translation", index: number, voice_id: string, } | { "type": "pause", millis: number, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 每天不发送提醒的时段，结束时间早于开始时间表示跨过午夜
+ */
+export type QuietHours = { enabled: boolean, 
+/**
+ * HH:MM，本地时间
+ */
+start: string, end: string, };
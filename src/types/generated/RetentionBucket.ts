@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 按复习间隔分组的记忆保持率：距离上次复习若干天后再次复习时答对的比例
+ */
+export type RetentionBucket = { label: string, min_days: number, 
+/**
+ * 不包含；None 表示不设上限
+ */
+max_days: number | null, reviews: number, recalled: number, 
+/**
+ * 0-1，没有复习时为 0
+ */
+retention: number, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 一次复习记录，每次评分追加一条，用于统计学习会话和学习分析
+ */
+export type ReviewRecord = { id: string, word_id: string, 
+/**
+ * 评分时正在进行的学习会话
+ */
+session_id: string | null, reviewed_at: string, 
+/**
+ * 该单词上一次复习的时间，首次学习为 None
+ */
+previous_review: string | null, is_correct: boolean, 
+/**
+ * 秒
+ */
+time_spent: number, progress: number, mastery_level: number, };
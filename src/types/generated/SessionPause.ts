@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 学习会话中的一次暂停，`ended_at` 为 None 表示仍在暂停
+ */
+export type SessionPause = { started_at: string, ended_at: string | null, };
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HotkeyConfig } from "./HotkeyConfig";
+import type { LearningConfig } from "./LearningConfig";
+import type { NotificationConfig } from "./NotificationConfig";
+import type { PausePolicy } from "./PausePolicy";
+import type { ThemeConfig } from "./ThemeConfig";
+import type { TtsConfig } from "./TtsConfig";
+import type { WindowSize } from "./WindowSize";
+
+export type Settings = { 
+/**
+ * 配置结构版本，见 utils::settings_migration
+ */
+version: number, auto_switch: boolean, switch_interval: number, 
+/**
+ * 自动切换的暂停条件
+ */
+pause_policy: PausePolicy, always_on_top: boolean, show_in_tray: boolean, enable_tts: boolean, tts: TtsConfig, 
+/**
+ * vocabulary 目录下的单词本文件名
+ */
+active_wordbook: string, window_size: WindowSize, hotkeys: HotkeyConfig, theme: ThemeConfig, learning: LearningConfig, notification: NotificationConfig, };
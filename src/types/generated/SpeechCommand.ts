@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 本地语音合成命令，程序把语音写入 WAV 文件后由应用播放
+ *
+ * 参数中的 `{text}` 替换为朗读内容，`{output}` 替换为输出文件路径，`{voice}` 替换为朗读步骤指定的语音；
+ * 参数中没有 `{text}` 时朗读内容通过标准输入传入（piper 的用法）。
+ */
+export type SpeechCommand = { 
+/**
+ * 空字符串表示未配置
+ */
+program: string, args: Array<string>, };
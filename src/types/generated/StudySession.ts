@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SessionPause } from "./SessionPause";
+import type { SessionType } from "./SessionType";
+
+/**
+ * 一次学习会话；答题数等统计由后端根据复习记录汇总
+ */
+export type StudySession = { session_id: string, session_type: SessionType, started_at: string, 
+/**
+ * None 表示会话仍在进行
+ */
+ended_at: string | null, pauses: Array<SessionPause>, 
+/**
+ * 学习的单词ID列表
+ */
+words_studied: Array<string>, 
+/**
+ * 会话时长（秒），不含暂停时间
+ */
+session_duration: number, correct_answers: number, total_answers: number, average_time_per_word: number, };
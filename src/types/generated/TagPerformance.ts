@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 一个标签下单词的答题情况
+ */
+export type TagPerformance = { tag: string, reviews: number, correct: number, accuracy: number, 
+/**
+ * 每次复习的平均用时（秒）
+ */
+average_time_spent: number, };
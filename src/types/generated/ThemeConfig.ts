@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThemeMode } from "./ThemeMode";
+
+export type ThemeConfig = { mode: ThemeMode, accent_color: string, use_mica_effect: boolean, transparency: number, font_family: string, font_size: number, };
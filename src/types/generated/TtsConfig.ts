@@ -0,0 +1,38 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Accent } from "./Accent";
+import type { PlaybackStep } from "./PlaybackStep";
+import type { PronunciationBackendKind } from "./PronunciationBackendKind";
+import type { SpeechCommand } from "./SpeechCommand";
+
+/**
+ * 朗读设置，语速和音调是相对于系统语音默认值的倍数
+ */
+export type TtsConfig = { 
+/**
+ * 空字符串表示按口音自动选择
+ */
+voice_id: string, rate: number, pitch: number, 
+/**
+ * 0-1
+ */
+volume: number, accent: Accent, 
+/**
+ * 朗读单词时依次播放的内容
+ */
+sequence: Array<PlaybackStep>, 
+/**
+ * 自动切换到新单词时朗读，便于不看屏幕学习
+ */
+speak_on_switch: boolean, 
+/**
+ * 每次朗读重复的遍数
+ */
+repeat: number, 
+/**
+ * 两遍之间的间隔（毫秒）
+ */
+repeat_delay: number, 
+/**
+ * 按顺序尝试的发音方式，前一个不可用或失败时使用下一个
+ */
+backends: Array<PronunciationBackendKind>, command: SpeechCommand, };
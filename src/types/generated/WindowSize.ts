@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RatioPreset } from "./RatioPreset";
+
+export type WindowSize = { width: number, height: number, ratio_preset: RatioPreset, };
@@ -1,34 +1,28 @@
-export interface WindowSize {
-  width: number
-  height: number
-  aspectRatio: string
-}
+// 后端设置（src-tauri/src/models/settings.rs），由 ts-rs 生成
+export type { Settings } from './generated/Settings'
+export type { PausePolicy } from './generated/PausePolicy'
+export type { WindowSize } from './generated/WindowSize'
+export type { HotkeyConfig } from './generated/HotkeyConfig'
+export type { HotkeyBinding } from './generated/HotkeyBinding'
+export type { ThemeConfig } from './generated/ThemeConfig'
+export type { LearningConfig } from './generated/LearningConfig'
+export type { NotificationConfig } from './generated/NotificationConfig'
+export type { QuietHours } from './generated/QuietHours'
+export type { ThemeMode } from './generated/ThemeMode'
+export type { FieldError } from './generated/FieldError'
 
-export interface HotkeyConfig {
-  nextWord: string
-  previousWord: string
-  playPronunciation: string
-  togglePause: string
-  toggleWindow: string
-  markMastered: string
-  showSettings: string
-}
+import type { AppAction } from './generated/AppAction'
 
-export interface ThemeConfig {
-  mode: 'light' | 'dark' | 'auto'
-  primaryColor: string
-  backgroundColor: string
+// 快捷键配置中的问题（src-tauri/src/utils/hotkey.rs 中的 HotkeyIssue），由 check_hotkeys 返回
+export interface HotkeyIssue {
+  action: AppAction
+  shortcut: string
+  kind: 'invalid' | 'unsafe' | 'duplicate' | 'conflict'
+  message: string
+  suggestions: string[]
 }
 
-export interface Settings {
-  autoSwitch: boolean       // 自动切换开关
-  switchInterval: number    // 切换间隔(秒)
-  alwaysOnTop: boolean     // 窗口置顶
-  showInTray: boolean      // 显示托盘图标
-  enableTTS: boolean       // 启用语音
-  enableHotkeys: boolean   // 启用快捷键
-  autoCheckUpdates: boolean // 自动检查更新
-  windowSize: WindowSize   // 窗口尺寸
-  hotkeys: HotkeyConfig    // 快捷键配置
-  theme: ThemeConfig       // 主题配置
-} 
\ No newline at end of file
+// 只在前端使用的偏好，保存在 localStorage
+export interface LocalPreferences {
+  autoCheckUpdates: boolean  // 自动检查更新
+}
@@ -0,0 +1,27 @@
+import type { PronunciationBackendKind } from './generated/PronunciationBackendKind'
+
+// 后端 Settings.tts
+export type { TtsConfig } from './generated/TtsConfig'
+
+// 后端 pronunciation::BackendStatus
+export interface BackendStatus {
+  kind: PronunciationBackendKind
+  enabled: boolean
+  configured: boolean
+  last_error: string | null
+}
+
+export interface VoiceInfo {
+  id: string
+  name: string
+  language: string
+}
+
+// speech-started / speech-finished 事件内容
+export interface SpeechEvent {
+  id: number
+  text: string
+  word_id: string | null
+  interrupted: boolean      // 被新的朗读或停止操作打断
+  error: string | null      // 所有发音方式都失败时的原因
+}
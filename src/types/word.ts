@@ -44,14 +44,6 @@ export interface LearningStats {
   total_time_spent: number
 }
 
-// 新增：学习会话数据模型
-export interface StudySession {
-  session_id?: string
-  timestamp?: string
-  words_studied: string[]   // 学习的单词ID列表
-  session_duration: number  // 会话时长（秒）
-  correct_answers: number
-  total_answers: number
-  average_time_per_word: number
-  session_type: 'review' | 'new_words' | 'mixed'
-} 
\ No newline at end of file
+// 学习会话数据模型，由后端根据复习记录汇总
+export type { StudySession } from './generated/StudySession'
+export type { SessionType } from './generated/SessionType'